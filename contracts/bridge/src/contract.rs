@@ -1,7 +1,8 @@
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{
-    get_proposal_id, BridgeInfo, Proposal, BRIDGE_INFO, PROPOSALS, RESOURCE_ID_TO_TOKEN,
+    get_proposal_id, BridgeInfo, PendingAdmin, Proposal, BRIDGE_INFO, PENDING_ADMIN, PROPOSALS,
+    RESOURCE_ID_TO_TOKEN,
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -10,6 +11,7 @@ use cosmwasm_std::{
     Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw_utils::Expiration;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:bridge";
@@ -43,7 +45,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -74,14 +76,22 @@ pub fn execute(
         ExecuteMsg::ChangeThreshold { threshold } => {
             execute_change_threshold(deps, info, threshold)
         }
-        ExecuteMsg::TransferAdmin { new_admin } => execute_transfer_admin(deps, info, new_admin),
+        ExecuteMsg::TransferAdmin { new_admin } => {
+            execute_transfer_admin(deps, env, info, new_admin)
+        }
+        ExecuteMsg::ProposeAdmin { new_admin, expires } => {
+            execute_propose_admin(deps, env, info, new_admin, expires)
+        }
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminTransfer {} => execute_cancel_admin_transfer(deps, info),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::BridgeInfo {} => to_json_binary(&query_bridge_info(deps)?),
+        QueryMsg::PendingAdmin {} => to_json_binary(&query_pending_admin(deps, env)?),
         QueryMsg::Proposal {
             chain_id,      // source chain id
             deposit_nonce, // deposit nonce from source chain
@@ -105,6 +115,13 @@ pub fn query_bridge_info(deps: Deps) -> StdResult<BridgeInfo> {
     Ok(bridge_info)
 }
 
+// an expired proposal counts as none
+pub fn query_pending_admin(deps: Deps, env: Env) -> StdResult<Option<PendingAdmin>> {
+    Ok(PENDING_ADMIN
+        .may_load(deps.storage)?
+        .filter(|pending_admin| !pending_admin.expires.is_expired(&env.block)))
+}
+
 pub fn query_proposal(
     deps: Deps,
     chain_id: u64,      // source chain id
@@ -299,6 +316,7 @@ pub fn execute_change_threshold(
 
 pub fn execute_transfer_admin(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_admin: String,
 ) -> Result<Response, ContractError> {
@@ -306,6 +324,9 @@ pub fn execute_transfer_admin(
     if bridge_info.admin != info.sender {
         return Err(ContractError::Unauthorized {});
     }
+    if query_pending_admin(deps.as_ref(), env)?.is_some() {
+        return Err(ContractError::PendingAdminExists {});
+    }
 
     if let Ok(admin) = deps.api.addr_validate(&new_admin) {
         bridge_info.admin = admin;
@@ -318,6 +339,83 @@ pub fn execute_transfer_admin(
     Ok(Response::default().add_attribute("action", "transfer_admin"))
 }
 
+pub fn execute_propose_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let bridge_info = BRIDGE_INFO.load(deps.storage)?;
+    if bridge_info.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let new_admin = deps
+        .api
+        .addr_validate(&new_admin)
+        .map_err(|_| ContractError::InvalidAddress {})?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    PENDING_ADMIN.save(
+        deps.storage,
+        &PendingAdmin {
+            new_admin: new_admin.clone(),
+            expires,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("new_admin", new_admin))
+}
+
+pub fn execute_accept_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending_admin = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+    if pending_admin.new_admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if pending_admin.expires.is_expired(&env.block) {
+        return Err(ContractError::PendingAdminExpired {});
+    }
+
+    let mut bridge_info = BRIDGE_INFO.load(deps.storage)?;
+    bridge_info.admin = pending_admin.new_admin;
+
+    BRIDGE_INFO.save(deps.storage, &bridge_info)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("admin", info.sender))
+}
+
+pub fn execute_cancel_admin_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let bridge_info = BRIDGE_INFO.load(deps.storage)?;
+    if bridge_info.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !PENDING_ADMIN.exists(deps.storage) {
+        return Err(ContractError::NoPendingAdmin {});
+    }
+
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::default().add_attribute("action", "cancel_admin_transfer"))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
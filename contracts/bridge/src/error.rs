@@ -23,4 +23,16 @@ pub enum ContractError {
 
     #[error("Already executed")]
     AlreadyExecuted {},
+
+    #[error("Admin transfer is pending")]
+    PendingAdminExists {},
+
+    #[error("No pending admin transfer")]
+    NoPendingAdmin {},
+
+    #[error("Invalid expiration")]
+    InvalidExpiration {},
+
+    #[error("Admin proposal expired")]
+    PendingAdminExpired {},
 }
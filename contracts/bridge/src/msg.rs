@@ -3,7 +3,9 @@ use cosmwasm_std::Addr;
 use cosmwasm_std::Uint128;
 
 use crate::state::BridgeInfo;
+use crate::state::PendingAdmin;
 use crate::state::Proposal;
+use cw_utils::Expiration;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -40,6 +42,12 @@ pub enum ExecuteMsg {
     TransferAdmin {
         new_admin: String,
     },
+    ProposeAdmin {
+        new_admin: String,
+        expires: Option<Expiration>,
+    },
+    AcceptAdmin {},
+    CancelAdminTransfer {},
 }
 
 #[cw_serde]
@@ -47,6 +55,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(BridgeInfo)]
     BridgeInfo {},
+    #[returns(Option<PendingAdmin>)]
+    PendingAdmin {},
     #[returns(Proposal)]
     Proposal {
         chain_id: u64,      // source chain id
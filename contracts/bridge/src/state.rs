@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map};
+use cw_utils::Expiration;
 use sha2::{
     digest::{Digest, Update},
     Sha256,
@@ -15,6 +16,14 @@ pub struct BridgeInfo {
 
 pub const BRIDGE_INFO: Item<BridgeInfo> = Item::new("bridge_info");
 
+#[cw_serde]
+pub struct PendingAdmin {
+    pub new_admin: Addr,
+    pub expires: Expiration,
+}
+
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+
 #[cw_serde]
 pub struct Proposal {
     pub chain_id: u64,
@@ -6,8 +6,8 @@ use crate::enumerable::{query_all_accounts, query_owner_allowances, query_spende
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{
-    MinterData, TokenInfo, ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, LOGO, MARKETING_INFO,
-    TOKEN_INFO,
+    MinterData, PendingAdmin, TokenInfo, ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, LOGO,
+    MARKETING_INFO, PENDING_ADMIN, TOKEN_INFO,
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -17,8 +17,8 @@ use cosmwasm_std::{
 };
 use cw2::{ensure_from_older_version, set_contract_version};
 use cw20::{
-    BalanceResponse, Cw20Coin, Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo,
-    MarketingInfoResponse, TokenInfoResponse,
+    BalanceResponse, Cw20Coin, Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Expiration,
+    Logo, LogoInfo, MarketingInfoResponse, TokenInfoResponse,
 };
 
 // version info for migration info
@@ -235,6 +235,11 @@ pub fn execute(
         ExecuteMsg::TransferAdmin { new_admin } => {
             execute_transfer_admin(deps, env, info, new_admin)
         }
+        ExecuteMsg::ProposeAdmin { new_admin, expires } => {
+            execute_propose_admin(deps, env, info, new_admin, expires)
+        }
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminTransfer {} => execute_cancel_admin_transfer(deps, info),
     }
 }
 
@@ -431,7 +436,7 @@ pub fn execute_remove_minter(
 
 pub fn execute_transfer_admin(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     new_admin: String,
 ) -> Result<Response, ContractError> {
@@ -443,6 +448,9 @@ pub fn execute_transfer_admin(
     if mint.admin != info.sender {
         return Err(ContractError::Unauthorized {});
     }
+    if query_pending_admin(deps.as_ref(), env)?.is_some() {
+        return Err(ContractError::PendingAdminExists {});
+    }
 
     if let Ok(admin) = deps.api.addr_validate(&new_admin) {
         mint.admin = admin;
@@ -455,6 +463,92 @@ pub fn execute_transfer_admin(
     Ok(Response::default().add_attribute("action", "transfer_admin"))
 }
 
+pub fn execute_propose_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let config = TOKEN_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    let mint = config.mint.ok_or(ContractError::Unauthorized {})?;
+    if mint.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let new_admin = deps
+        .api
+        .addr_validate(&new_admin)
+        .map_err(|_| ContractError::InvalidAddress {})?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    PENDING_ADMIN.save(
+        deps.storage,
+        &PendingAdmin {
+            new_admin: new_admin.clone(),
+            expires,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("new_admin", new_admin))
+}
+
+pub fn execute_accept_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending_admin = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+    if pending_admin.new_admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if pending_admin.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    let mut config = TOKEN_INFO.load(deps.storage)?;
+    let mint = config.mint.as_mut().ok_or(ContractError::Unauthorized {})?;
+    mint.admin = pending_admin.new_admin;
+
+    TOKEN_INFO.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("admin", info.sender))
+}
+
+pub fn execute_cancel_admin_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = TOKEN_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    let mint = config.mint.ok_or(ContractError::Unauthorized {})?;
+    if mint.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !PENDING_ADMIN.exists(deps.storage) {
+        return Err(ContractError::NoPendingAdmin {});
+    }
+
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::default().add_attribute("action", "cancel_admin_transfer"))
+}
+
 pub fn execute_update_marketing(
     deps: DepsMut,
     _env: Env,
@@ -544,11 +638,12 @@ pub fn execute_upload_logo(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Balance { address } => to_json_binary(&query_balance(deps, address)?),
         QueryMsg::TokenInfo {} => to_json_binary(&query_token_info(deps)?),
         QueryMsg::Minter {} => to_json_binary(&query_minter(deps)?),
+        QueryMsg::PendingAdmin {} => to_json_binary(&query_pending_admin(deps, env)?),
         QueryMsg::Allowance { owner, spender } => {
             to_json_binary(&query_allowance(deps, owner, spender)?)
         }
@@ -607,6 +702,13 @@ pub fn query_minter(deps: Deps) -> StdResult<Option<MinterData>> {
     Ok(minter)
 }
 
+// an expired proposal counts as none
+pub fn query_pending_admin(deps: Deps, env: Env) -> StdResult<Option<PendingAdmin>> {
+    Ok(PENDING_ADMIN
+        .may_load(deps.storage)?
+        .filter(|pending_admin| !pending_admin.expires.is_expired(&env.block)))
+}
+
 pub fn query_marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
     Ok(MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default())
 }
@@ -1068,6 +1170,140 @@ mod tests {
     //     assert_eq!(err, ContractError::Unauthorized {});
     // }
 
+    #[test]
+    fn two_step_admin_transfer() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        do_instantiate_with_minter(
+            deps.as_mut(),
+            &String::from("genesis"),
+            Uint128::new(1234),
+            &minter,
+            None,
+        );
+        let env = mock_env();
+
+        // only the admin proposes
+        let msg = ExecuteMsg::ProposeAdmin {
+            new_admin: String::from("new_admin"),
+            expires: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap();
+        assert_eq!(
+            query_pending_admin(deps.as_ref(), env.clone()).unwrap(),
+            Some(PendingAdmin {
+                new_admin: Addr::unchecked("new_admin"),
+                expires: Expiration::Never {},
+            })
+        );
+
+        // the one step transfer is blocked while a proposal is pending
+        let msg = ExecuteMsg::TransferAdmin {
+            new_admin: String::from("other"),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::PendingAdminExists {});
+
+        // only the proposed admin accepts
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("other", &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("new_admin", &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap();
+        assert_eq!(
+            query_minter(deps.as_ref()).unwrap().unwrap().admin,
+            Addr::unchecked("new_admin")
+        );
+        assert_eq!(query_pending_admin(deps.as_ref(), env).unwrap(), None);
+    }
+
+    #[test]
+    fn cancel_and_expire_admin_proposal() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        do_instantiate_with_minter(
+            deps.as_mut(),
+            &String::from("genesis"),
+            Uint128::new(1234),
+            &minter,
+            None,
+        );
+        let mut env = mock_env();
+
+        // an expired expiry is rejected
+        let msg = ExecuteMsg::ProposeAdmin {
+            new_admin: String::from("new_admin"),
+            expires: Some(Expiration::AtHeight(env.block.height)),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidExpiration {});
+
+        let msg = ExecuteMsg::ProposeAdmin {
+            new_admin: String::from("new_admin"),
+            expires: Some(Expiration::AtHeight(env.block.height + 10)),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&minter, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&minter, &[]),
+            ExecuteMsg::CancelAdminTransfer {},
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("new_admin", &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoPendingAdmin {});
+
+        // an expired proposal can't be accepted and no longer blocks the one step transfer
+        execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap();
+        env.block.height += 10;
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("new_admin", &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Expired {});
+        let msg = ExecuteMsg::TransferAdmin {
+            new_admin: String::from("other"),
+        };
+        execute(deps.as_mut(), env, mock_info(&minter, &[]), msg).unwrap();
+        assert_eq!(
+            query_minter(deps.as_ref()).unwrap().unwrap().admin,
+            Addr::unchecked("other")
+        );
+    }
+
     #[test]
     fn no_one_mints_if_minter_unset() {
         let mut deps = mock_dependencies();
@@ -43,4 +43,10 @@ pub enum ContractError {
 
     #[error("Invalid address")]
     InvalidAddress {},
+
+    #[error("Admin transfer is pending")]
+    PendingAdminExists {},
+
+    #[error("No pending admin transfer")]
+    NoPendingAdmin {},
 }
@@ -147,6 +147,16 @@ pub enum ExecuteMsg {
     TransferAdmin {
         new_admin: String,
     },
+    /// Only with the "mintable" extension. The admin proposes a new admin, who takes
+    /// over by accepting before expires. A pending proposal blocks TransferAdmin.
+    ProposeAdmin {
+        new_admin: String,
+        expires: Option<Expiration>,
+    },
+    /// The proposed admin accepts the admin role.
+    AcceptAdmin {},
+    /// The admin drops the pending proposal.
+    CancelAdminTransfer {},
     /// Only with the "marketing" extension. If authorized, updates marketing metadata.
     /// Setting None/null for any of these will leave it unchanged.
     /// Setting Some("") will clear this field on the contract storage
@@ -175,6 +185,10 @@ pub enum QueryMsg {
     /// Returns who can mint and the hard cap on maximum tokens after minting.
     #[returns(cw20::MinterResponse)]
     Minter {},
+    /// Only with "mintable" extension.
+    /// Returns the proposed admin, None if there is no live proposal.
+    #[returns(Option<crate::state::PendingAdmin>)]
+    PendingAdmin {},
     /// Only with "allowance" extension.
     /// Returns how much spender can use from owner account, 0 if unset.
     #[returns(cw20::AllowanceResponse)]
@@ -2,7 +2,7 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map};
 
-use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
+use cw20::{AllowanceResponse, Expiration, Logo, MarketingInfoResponse};
 
 #[cw_serde]
 pub struct TokenInfo {
@@ -21,6 +21,12 @@ pub struct MinterData {
     pub cap: Option<Uint128>,
 }
 
+#[cw_serde]
+pub struct PendingAdmin {
+    pub new_admin: Addr,
+    pub expires: Expiration,
+}
+
 impl TokenInfo {
     pub fn get_cap(&self) -> Option<Uint128> {
         self.mint.as_ref().and_then(|v| v.cap)
@@ -30,6 +36,7 @@ impl TokenInfo {
 pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
 pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
 pub const LOGO: Item<Logo> = Item::new("logo");
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
 pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
 pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
 // TODO: After https://github.com/CosmWasm/cw-plus/issues/670 is implemented, replace this with a `MultiIndex` over `ALLOWANCES`
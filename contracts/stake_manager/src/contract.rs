@@ -1,37 +1,58 @@
-use crate::execute_era_active::execute_era_active;
+use crate::execute_admin_transfer::{
+    execute_accept_pool_admin, execute_accept_stack_admin, execute_cancel_pool_admin_transfer,
+    execute_cancel_stack_admin_transfer, execute_propose_pool_admin, execute_propose_stack_admin,
+};
+use crate::execute_era_active::{execute_acknowledge_slash, execute_era_active};
 use crate::execute_era_bond::execute_era_bond;
 use crate::execute_era_collect_withdraw::execute_era_collect_withdraw;
+use crate::execute_era_process::execute_era_process;
 use crate::execute_era_rebond::execute_era_rebond;
 use crate::execute_era_update::execute_era_update;
 use crate::execute_icq_update_period::update_icq_update_period;
 use crate::execute_init_pool::execute_init_pool;
+use crate::execute_instant_unstake::execute_instant_unstake;
 use crate::execute_migrate_pool::execute_migrate_pool;
-use crate::execute_open_channel::execute_open_channel;
+use crate::execute_open_channel::{execute_open_channel, execute_reopen_channel};
 use crate::execute_pool_add_validator::execute_add_pool_validators;
 use crate::execute_pool_delegate::execute_pool_delegate;
+use crate::execute_pool_rebalance::execute_pool_rebalance;
 use crate::execute_pool_rm_validator::execute_rm_pool_validator;
 use crate::execute_pool_update_validator::execute_pool_update_validator;
 use crate::execute_redeem_token_for_share::execute_redeem_token_for_share;
 use crate::execute_register_pool::{execute_register_pool, sudo_open_ack};
+use crate::execute_resolve_pending_tx::execute_resolve_pending_tx;
+use crate::execute_roles::{execute_grant_role, execute_revoke_role};
 use crate::execute_stake::execute_stake;
 use crate::execute_stake_lsm::execute_stake_lsm;
 use crate::execute_unstake::execute_unstake;
-use crate::execute_withdraw::execute_withdraw;
+use crate::execute_withdraw::{execute_resolve_neutron_withdraw, execute_withdraw};
+use crate::execute_withdraw_for::{execute_set_withdraw_receiver, execute_withdraw_for};
 use crate::helper::{
     QUERY_REPLY_ID_RANGE_END, QUERY_REPLY_ID_RANGE_START, REPLY_ID_RANGE_END, REPLY_ID_RANGE_START,
 };
+use crate::migrate_sudo_payload::migrate_sudo_payloads;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::query::query_user_unstake_index;
+use crate::query::query_validator_status_events;
+use crate::query::query_withdraw_receiver;
+use crate::query::{query_apr, query_delegation_by_addr, query_era_rate, query_era_rates};
 use crate::query::{query_balance_by_addr, query_validator_by_addr};
-use crate::query::{query_delegation_by_addr, query_era_rate};
-use crate::query::{query_era_snapshot, query_ids};
+use crate::query::{query_era_snapshot, query_ids, query_redelegation_limits, query_slash_events};
 use crate::query::{
     query_interchain_address, query_interchain_address_contract, query_pool_info,
     query_user_unstake,
 };
-use crate::query::{query_stack_info, query_total_stack_fee};
+use crate::query::{query_pending_pool_admin, query_pending_stack_admin, query_roles};
+use crate::query::{
+    query_pending_txs, query_simulate_stake, query_simulate_unstake, query_simulate_withdraw,
+};
+use crate::query::{
+    query_pool_health, query_pool_unstakes, query_pools, query_withdrawable_unstakes,
+};
+use crate::query::{query_stack_info, query_total_stack_fee, query_validator_unbond_slots};
 use crate::query_callback::write_reply_id_to_query_id;
-use crate::state::{Stack, STACK};
+use crate::state::migrate_unstake_indexes;
+use crate::state::{Stack, WithdrawMode, STACK};
 use crate::tx_callback::{prepare_sudo_payload, sudo_error, sudo_response, sudo_timeout};
 use crate::{error_conversion::ContractError, query_callback::sudo_kv_query_result};
 use crate::{execute_config_pool::execute_config_pool, query::get_ica_registered_query};
@@ -81,7 +102,13 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+    let (migrated, failed) = migrate_sudo_payloads(deps.storage)?;
+    let indexed_unstakes = migrate_unstake_indexes(deps.storage)?;
+    Ok(Response::default()
+        .add_attribute("action", "migrate")
+        .add_attribute("indexed_unstakes", indexed_unstakes.to_string())
+        .add_attribute("migrated_sudo_payloads", migrated.to_string())
+        .add_attribute("failed_sudo_payloads", failed.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -117,6 +144,13 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> NeutronResult
         }
         QueryMsg::PoolInfo { pool_addr } => query_pool_info(deps, env, pool_addr),
         QueryMsg::StackInfo {} => query_stack_info(deps),
+        QueryMsg::PendingStackAdmin {} => query_pending_stack_admin(deps, env),
+        QueryMsg::PendingPoolAdmin { pool_addr } => query_pending_pool_admin(deps, env, pool_addr),
+        QueryMsg::Roles {
+            pool_addr,
+            start_after,
+            limit,
+        } => query_roles(deps, pool_addr, start_after, limit),
         QueryMsg::TotalStackFee { pool_addr } => query_total_stack_fee(deps, pool_addr),
         QueryMsg::EraSnapshot { pool_addr } => query_era_snapshot(deps, env, pool_addr),
         QueryMsg::InterchainAccountAddress {
@@ -134,8 +168,65 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> NeutronResult
             pool_addr,
             user_neutron_addr,
         } => query_user_unstake_index(deps, pool_addr, user_neutron_addr),
+        QueryMsg::WithdrawReceiver {
+            pool_addr,
+            user_neutron_addr,
+        } => query_withdraw_receiver(deps, pool_addr, user_neutron_addr),
         QueryMsg::EraRate { pool_addr, era } => query_era_rate(deps, pool_addr, era),
+        QueryMsg::EraRates {
+            pool_addr,
+            start_after,
+            limit,
+        } => query_era_rates(deps, pool_addr, start_after, limit),
+        QueryMsg::Apr {
+            pool_addr,
+            window_eras,
+        } => query_apr(deps, pool_addr, window_eras),
         QueryMsg::QueryIds { pool_addr } => query_ids(deps, pool_addr),
+        QueryMsg::RedelegationLimits { pool_addr } => {
+            query_redelegation_limits(deps, env, pool_addr)
+        }
+        QueryMsg::ValidatorUnbondSlots { pool_addr } => {
+            query_validator_unbond_slots(deps, env, pool_addr)
+        }
+        QueryMsg::SlashEvents {
+            pool_addr,
+            start_after,
+            limit,
+        } => query_slash_events(deps, pool_addr, start_after, limit),
+        QueryMsg::PendingTxs { start_after, limit } => {
+            query_pending_txs(deps, env, start_after, limit)
+        }
+        QueryMsg::SimulateUnstake {
+            pool_addr,
+            user_neutron_addr,
+            lsd_token_amount,
+        } => query_simulate_unstake(deps, env, pool_addr, user_neutron_addr, lsd_token_amount),
+        QueryMsg::PoolHealth { pool_addr } => query_pool_health(deps, env, pool_addr),
+        QueryMsg::Pools { start_after, limit } => query_pools(deps, start_after, limit),
+        QueryMsg::PoolUnstakes {
+            pool_addr,
+            status,
+            start_after,
+            limit,
+        } => query_pool_unstakes(deps, pool_addr, status, start_after, limit),
+        QueryMsg::WithdrawableUnstakes { pool_addr, user } => {
+            query_withdrawable_unstakes(deps, pool_addr, user)
+        }
+        QueryMsg::SimulateStake {
+            pool_addr,
+            token_amount,
+        } => query_simulate_stake(deps, pool_addr, token_amount),
+        QueryMsg::SimulateWithdraw {
+            pool_addr,
+            user_neutron_addr,
+            unstake_index_list,
+        } => query_simulate_withdraw(deps, pool_addr, user_neutron_addr, unstake_index_list),
+        QueryMsg::ValidatorStatusEvents {
+            pool_addr,
+            start_after,
+            limit,
+        } => query_validator_status_events(deps, pool_addr, start_after, limit),
     }
 }
 
@@ -154,11 +245,38 @@ pub fn execute(
         ExecuteMsg::InitPool(params) => execute_init_pool(deps, env, info, *params),
         ExecuteMsg::MigratePool(params) => execute_migrate_pool(deps, env, info, *params),
         ExecuteMsg::ConfigPool(params) => execute_config_pool(deps, info, env, *params),
-        ExecuteMsg::ConfigStack(params) => execute_config_stack(deps, info, *params),
+        ExecuteMsg::ConfigStack(params) => execute_config_stack(deps, env, info, *params),
+        ExecuteMsg::ProposeStackAdmin { new_admin, expires } => {
+            execute_propose_stack_admin(deps, env, info, new_admin, expires)
+        }
+        ExecuteMsg::AcceptStackAdmin {} => execute_accept_stack_admin(deps, env, info),
+        ExecuteMsg::CancelStackAdminTransfer {} => execute_cancel_stack_admin_transfer(deps, info),
+        ExecuteMsg::ProposePoolAdmin {
+            pool_addr,
+            new_admin,
+            expires,
+        } => execute_propose_pool_admin(deps, env, info, pool_addr, new_admin, expires),
+        ExecuteMsg::AcceptPoolAdmin { pool_addr } => {
+            execute_accept_pool_admin(deps, env, info, pool_addr)
+        }
+        ExecuteMsg::CancelPoolAdminTransfer { pool_addr } => {
+            execute_cancel_pool_admin_transfer(deps, info, pool_addr)
+        }
         ExecuteMsg::OpenChannel {
             pool_addr,
             closed_channel_id,
         } => execute_open_channel(deps, env, info, pool_addr, closed_channel_id),
+        ExecuteMsg::GrantRole {
+            pool_addr,
+            account,
+            role,
+        } => execute_grant_role(deps, info, pool_addr, account, role),
+        ExecuteMsg::RevokeRole {
+            pool_addr,
+            account,
+            role,
+        } => execute_revoke_role(deps, info, pool_addr, account, role),
+        ExecuteMsg::ReopenChannel { pool_addr } => execute_reopen_channel(deps, info, pool_addr),
         ExecuteMsg::RedeemTokenForShare { pool_addr, tokens } => {
             execute_redeem_token_for_share(deps, info, pool_addr, tokens)
         }
@@ -166,16 +284,65 @@ pub fn execute(
             neutron_address,
             pool_addr,
         } => execute_stake(deps, env, neutron_address, pool_addr, info),
-        ExecuteMsg::Unstake { amount, pool_addr } => execute_unstake(deps, info, amount, pool_addr),
+        ExecuteMsg::Unstake { amount, pool_addr } => {
+            execute_unstake(deps, env, info, amount, pool_addr)
+        }
+        ExecuteMsg::InstantUnstake { amount, pool_addr } => {
+            execute_instant_unstake(deps, info, amount, pool_addr)
+        }
         ExecuteMsg::Withdraw {
             pool_addr,
             receiver,
             unstake_index_list,
-        } => execute_withdraw(deps, info, pool_addr, receiver, unstake_index_list),
+            mode,
+        } => execute_withdraw(
+            deps,
+            env,
+            info,
+            pool_addr,
+            receiver,
+            unstake_index_list,
+            mode.unwrap_or(WithdrawMode::HostChain),
+        ),
+        ExecuteMsg::ResolvePendingTx {
+            channel_id,
+            seq_id,
+            outcome,
+            force,
+        } => execute_resolve_pending_tx(
+            deps,
+            env,
+            info,
+            channel_id,
+            seq_id,
+            outcome,
+            force.unwrap_or(false),
+        ),
+        ExecuteMsg::ResolveNeutronWithdraw {
+            pool_addr,
+            unstake_index_list,
+            outcome,
+        } => execute_resolve_neutron_withdraw(
+            deps,
+            env,
+            info,
+            pool_addr,
+            unstake_index_list,
+            outcome,
+        ),
+        ExecuteMsg::SetWithdrawReceiver {
+            pool_addr,
+            receiver,
+        } => execute_set_withdraw_receiver(deps, info, pool_addr, receiver),
+        ExecuteMsg::WithdrawFor {
+            pool_addr,
+            users,
+            tip_receiver,
+        } => execute_withdraw_for(deps, info, pool_addr, users, tip_receiver),
         ExecuteMsg::PoolRmValidator {
             pool_addr,
             validator_addr,
-        } => execute_rm_pool_validator(deps, info, pool_addr, validator_addr),
+        } => execute_rm_pool_validator(deps, env, info, pool_addr, validator_addr),
         ExecuteMsg::PoolAddValidator {
             pool_addr,
             validator_addr,
@@ -184,7 +351,9 @@ pub fn execute(
             pool_addr,
             old_validator,
             new_validator,
-        } => execute_pool_update_validator(deps, info, pool_addr, old_validator, new_validator),
+        } => {
+            execute_pool_update_validator(deps, env, info, pool_addr, old_validator, new_validator)
+        }
         ExecuteMsg::PoolUpdateValidatorsIcq { pool_addr } => {
             execute_update_validators_icq(deps, env, info, pool_addr)
         }
@@ -192,19 +361,20 @@ pub fn execute(
             pool_addr,
             stake_amount,
         } => execute_pool_delegate(deps, info, pool_addr, stake_amount),
+        ExecuteMsg::PoolRebalance { pool_addr } => {
+            execute_pool_rebalance(deps, env, info, pool_addr)
+        }
         ExecuteMsg::EraUpdate { pool_addr } => execute_era_update(deps, env, info, pool_addr),
-        ExecuteMsg::EraBond {
-            pool_addr,
-            select_vals,
-        } => execute_era_bond(deps, env, info, pool_addr, select_vals),
+        ExecuteMsg::EraBond { pool_addr } => execute_era_bond(deps, env, info, pool_addr),
         ExecuteMsg::EraCollectWithdraw { pool_addr } => {
             execute_era_collect_withdraw(deps, info, pool_addr)
         }
-        ExecuteMsg::EraRebond {
-            pool_addr,
-            select_vals,
-        } => execute_era_rebond(deps, info, pool_addr, select_vals),
-        ExecuteMsg::EraActive { pool_addr } => execute_era_active(deps, pool_addr),
+        ExecuteMsg::EraRebond { pool_addr } => execute_era_rebond(deps, env, info, pool_addr),
+        ExecuteMsg::EraActive { pool_addr } => execute_era_active(deps, env, info, pool_addr),
+        ExecuteMsg::AcknowledgeSlash { pool_addr } => {
+            execute_acknowledge_slash(deps, info, pool_addr)
+        }
+        ExecuteMsg::EraProcess { pool_addr } => execute_era_process(deps, env, info, pool_addr),
         ExecuteMsg::StakeLsm {
             neutron_address,
             pool_addr,
@@ -20,6 +20,9 @@ pub enum ContractError {
     #[error("Rate change over limit")]
     RateChangeOverLimit {},
 
+    #[error("No slash waiting for acknowledgement")]
+    SlashNotPending {},
+
     #[error("Encode error: {0}")]
     EncodeError(String),
 
@@ -53,6 +56,33 @@ pub enum ContractError {
     #[error("Closed channel ID unmatch")]
     ClosedChannelIdUnmatch {},
 
+    #[error("Ica channel {0} is closed, reopen it first")]
+    IcaChannelClosed(String),
+
+    #[error("Ica channel is not closed")]
+    IcaChannelNotClosed {},
+
+    #[error("Tx timeout out of range")]
+    TxTimeoutOutOfRange {},
+
+    #[error("Duplicate tx timeout")]
+    DuplicateTxTimeout {},
+
+    #[error("Extra reward denom {0} not match")]
+    ExtraRewardDenomNotMatch(String),
+
+    #[error("Extra reward denoms over limit")]
+    ExtraRewardDenomsOverLimit {},
+
+    #[error("Extra reward route not match")]
+    ExtraRewardRouteNotMatch {},
+
+    #[error("Fee mode not match")]
+    FeeModeNotMatch {},
+
+    #[error("Unbond commission schedule not match")]
+    UnbondCommissionScheduleNotMatch {},
+
     #[error("Era process not end")]
     EraProcessNotEnd {},
 
@@ -62,6 +92,9 @@ pub enum ContractError {
     #[error("Validators empty")]
     ValidatorsEmpty {},
 
+    #[error("Delegate plan not cover the amount")]
+    DelegatePlanNotCovered {},
+
     #[error("Old validator not exist")]
     OldValidatorNotExist {},
 
@@ -181,6 +214,90 @@ pub enum ContractError {
 
     #[error("Period too small")]
     PeriodTooSmall {},
+
+    #[error("Validator weights sum not match")]
+    ValidatorWeightsSumNotMatch {},
+
+    #[error("Duplicate validator weight")]
+    DuplicateValidatorWeight {},
+
+    #[error("Rebalance tolerance over limit")]
+    RebalanceToleranceOverLimit {},
+
+    #[error("Delegations drift within tolerance")]
+    DriftWithinTolerance {},
+
+    #[error("Redelegation from {0} is transitive until {1}")]
+    TransitiveRedelegation(String, u64),
+
+    #[error("Redelegation entries from {0} to {1} reach max")]
+    RedelegationEntriesFull(String, String),
+
+    #[error("Max entries must be greater than zero")]
+    MaxEntriesZero {},
+
+    #[error("Liquidity buffer ratio over limit")]
+    LiquidityBufferRatioOverLimit {},
+
+    #[error("Instant unstake fee invalid")]
+    InstantUnstakeFeeInvalid {},
+
+    #[error("Liquidity buffer not enough")]
+    LiquidityBufferNotEnough {},
+
+    #[error("Unstake nft already set")]
+    UnstakeNftAlreadySet {},
+
+    #[error("Unstake index: {0} nft owner not match")]
+    UnstakeIndexNftOwnerNotMatch(u64),
+
+    #[error("Withdraw receiver of {0} not set")]
+    WithdrawReceiverNotSet(String),
+
+    #[error("Withdraw for tip over limit")]
+    WithdrawForTipOverLimit {},
+
+    #[error("Withdraw for users over limit")]
+    WithdrawForUsersOverLimit {},
+
+    #[error("Host transfer channel not set")]
+    HostTransferChannelNotSet {},
+
+    #[error("Unstake index: {0} transfer not expired")]
+    UnstakeIndexTransferNotExpired(u64),
+
+    #[error("Sudo payload data not match tx type")]
+    SudoPayloadNotMatch {},
+
+    #[error("Pending tx not exist")]
+    PendingTxNotExist {},
+
+    #[error("Pending tx not expired")]
+    PendingTxNotExpired {},
+
+    #[error("Pending tx deadline unknown, resolve it with force")]
+    PendingTxDeadlineUnknown {},
+
+    #[error("Apr window eras is zero")]
+    AprWindowErasZero {},
+
+    #[error("Admin transfer is pending")]
+    PendingAdminExists {},
+
+    #[error("No pending admin transfer")]
+    NoPendingAdmin {},
+
+    #[error("Admin proposal expired")]
+    PendingAdminExpired {},
+
+    #[error("Invalid expiration")]
+    InvalidExpiration {},
+
+    #[error("Role already granted")]
+    RoleAlreadyGranted {},
+
+    #[error("Role not granted")]
+    RoleNotGranted {},
 }
 
 impl From<ContractError> for NeutronError {
@@ -0,0 +1,177 @@
+use crate::error_conversion::ContractError;
+use crate::state::{PendingAdmin, PENDING_POOL_ADMINS, PENDING_STACK_ADMIN, POOLS, STACK};
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+use cw_utils::Expiration;
+use neutron_sdk::{
+    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    NeutronResult,
+};
+
+// an expired proposal counts as none
+pub fn pending_stack_admin(storage: &dyn Storage, env: &Env) -> StdResult<Option<PendingAdmin>> {
+    Ok(PENDING_STACK_ADMIN
+        .may_load(storage)?
+        .filter(|pending_admin| !pending_admin.expires.is_expired(&env.block)))
+}
+
+pub fn pending_pool_admin(
+    storage: &dyn Storage,
+    env: &Env,
+    pool_addr: String,
+) -> StdResult<Option<PendingAdmin>> {
+    Ok(PENDING_POOL_ADMINS
+        .may_load(storage, pool_addr)?
+        .filter(|pending_admin| !pending_admin.expires.is_expired(&env.block)))
+}
+
+fn new_pending_admin(
+    deps: &DepsMut<NeutronQuery>,
+    env: &Env,
+    new_admin: Addr,
+    expires: Option<Expiration>,
+) -> NeutronResult<PendingAdmin> {
+    deps.api.addr_validate(new_admin.as_str())?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {}.into());
+    }
+
+    Ok(PendingAdmin { new_admin, expires })
+}
+
+fn check_acceptable(
+    pending_admin: Option<PendingAdmin>,
+    env: &Env,
+    info: &MessageInfo,
+) -> NeutronResult<PendingAdmin> {
+    let Some(pending_admin) = pending_admin else {
+        return Err(ContractError::NoPendingAdmin {}.into());
+    };
+    if pending_admin.new_admin != info.sender {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+    if pending_admin.expires.is_expired(&env.block) {
+        return Err(ContractError::PendingAdminExpired {}.into());
+    }
+
+    Ok(pending_admin)
+}
+
+pub fn execute_propose_stack_admin(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    new_admin: Addr,
+    expires: Option<Expiration>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let stack = STACK.load(deps.storage)?;
+    if stack.admin != info.sender {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+
+    let pending_admin = new_pending_admin(&deps, &env, new_admin, expires)?;
+    PENDING_STACK_ADMIN.save(deps.storage, &pending_admin)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "propose_stack_admin")
+        .add_attribute("new_admin", pending_admin.new_admin))
+}
+
+pub fn execute_accept_stack_admin(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let pending_admin = check_acceptable(PENDING_STACK_ADMIN.may_load(deps.storage)?, &env, &info)?;
+
+    let mut stack = STACK.load(deps.storage)?;
+    stack.admin = pending_admin.new_admin;
+    STACK.save(deps.storage, &stack)?;
+    PENDING_STACK_ADMIN.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("action", "accept_stack_admin")
+        .add_attribute("admin", info.sender))
+}
+
+pub fn execute_cancel_stack_admin_transfer(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let stack = STACK.load(deps.storage)?;
+    if stack.admin != info.sender {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+    if !PENDING_STACK_ADMIN.exists(deps.storage) {
+        return Err(ContractError::NoPendingAdmin {}.into());
+    }
+
+    PENDING_STACK_ADMIN.remove(deps.storage);
+
+    Ok(Response::default().add_attribute("action", "cancel_stack_admin_transfer"))
+}
+
+pub fn execute_propose_pool_admin(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    pool_addr: String,
+    new_admin: Addr,
+    expires: Option<Expiration>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    if pool_info.admin != info.sender {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+
+    let pending_admin = new_pending_admin(&deps, &env, new_admin, expires)?;
+    PENDING_POOL_ADMINS.save(deps.storage, pool_addr.clone(), &pending_admin)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "propose_pool_admin")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("new_admin", pending_admin.new_admin))
+}
+
+pub fn execute_accept_pool_admin(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    pool_addr: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let pending_admin = check_acceptable(
+        PENDING_POOL_ADMINS.may_load(deps.storage, pool_addr.clone())?,
+        &env,
+        &info,
+    )?;
+
+    let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    pool_info.admin = pending_admin.new_admin;
+    POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
+    PENDING_POOL_ADMINS.remove(deps.storage, pool_addr.clone());
+
+    Ok(Response::default()
+        .add_attribute("action", "accept_pool_admin")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("admin", info.sender))
+}
+
+pub fn execute_cancel_pool_admin_transfer(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    pool_addr: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    if pool_info.admin != info.sender {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+    if !PENDING_POOL_ADMINS.has(deps.storage, pool_addr.clone()) {
+        return Err(ContractError::NoPendingAdmin {}.into());
+    }
+
+    PENDING_POOL_ADMINS.remove(deps.storage, pool_addr.clone());
+
+    Ok(Response::default()
+        .add_attribute("action", "cancel_pool_admin_transfer")
+        .add_attribute("pool", pool_addr))
+}
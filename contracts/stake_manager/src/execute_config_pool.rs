@@ -1,9 +1,17 @@
+use crate::execute_admin_transfer::pending_pool_admin;
+use crate::execute_roles::check_config_pool_roles;
+use crate::helper::MIN_ERA_SECONDS;
+use crate::state::{ExtraRewardRoute, FeeMode, QueryKind, INFO_OF_ICA_ID, POOLS};
+use crate::unbond_commission::check_unbond_commission_schedule;
 use crate::{
     error_conversion::ContractError,
-    helper::{MAX_ERA_SECONDS, UNBONDING_SECONDS},
+    helper::{
+        get_query_id, withdraw_balances_kv_keys, CAL_BASE, MAX_ERA_SECONDS,
+        MAX_EXTRA_REWARD_DENOMS, MAX_TX_TIMEOUT_SECONDS, MAX_WITHDRAW_FOR_TIP,
+        MIN_TX_TIMEOUT_SECONDS, UNBONDING_SECONDS, WEIGHT_BASE,
+    },
     msg::ConfigPoolParams,
 };
-use crate::{helper::MIN_ERA_SECONDS, state::POOLS};
 use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
@@ -19,9 +27,7 @@ pub fn execute_config_pool(
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.as_ref().storage, param.pool_addr.clone())?;
 
-    if info.sender != pool_info.admin {
-        return Err(ContractError::Unauthorized {}.into());
-    }
+    check_config_pool_roles(deps.storage, &pool_info, &info.sender, &param)?;
 
     if let Some(minimal_stake) = param.minimal_stake {
         pool_info.minimal_stake = minimal_stake;
@@ -35,6 +41,10 @@ pub fn execute_config_pool(
     if let Some(unbond_commission) = param.unbond_commission {
         pool_info.unbond_commission = unbond_commission;
     }
+    if let Some(unbond_commission_schedule) = param.unbond_commission_schedule {
+        check_unbond_commission_schedule(&unbond_commission_schedule)?;
+        pool_info.unbond_commission_schedule = unbond_commission_schedule;
+    }
     if let Some(platform_fee_commission) = param.platform_fee_commission {
         pool_info.platform_fee_commission = platform_fee_commission;
     }
@@ -78,10 +88,178 @@ pub fn execute_config_pool(
         pool_info.rate_change_limit = rate_change_limit;
     }
     if let Some(new_admin) = param.new_admin {
+        if pending_pool_admin(deps.storage, &env, param.pool_addr.clone())?.is_some() {
+            return Err(ContractError::PendingAdminExists {}.into());
+        }
         pool_info.admin = new_admin;
     }
+    if let Some(validator_select_strategy) = param.validator_select_strategy {
+        pool_info.validator_select_strategy = validator_select_strategy;
+    }
+    if let Some(validator_weights) = param.validator_weights {
+        // an empty list means equal weights
+        if !validator_weights.is_empty() {
+            let mut total_weight = 0u64;
+            let mut validators = vec![];
+            for validator_weight in validator_weights.iter() {
+                if !pool_info
+                    .validator_addrs
+                    .contains(&validator_weight.validator)
+                {
+                    return Err(ContractError::ValidatorNotSupport {}.into());
+                }
+                if validators.contains(&validator_weight.validator) {
+                    return Err(ContractError::DuplicateValidatorWeight {}.into());
+                }
+                validators.push(validator_weight.validator.clone());
+                total_weight = total_weight.saturating_add(validator_weight.weight);
+            }
+            if total_weight != WEIGHT_BASE {
+                return Err(ContractError::ValidatorWeightsSumNotMatch {}.into());
+            }
+        }
+        pool_info.validator_weights = validator_weights;
+    }
+    if let Some(rebalance_tolerance) = param.rebalance_tolerance {
+        if rebalance_tolerance > WEIGHT_BASE {
+            return Err(ContractError::RebalanceToleranceOverLimit {}.into());
+        }
+        pool_info.rebalance_tolerance = rebalance_tolerance;
+    }
+    if let Some(max_entries) = param.max_entries {
+        if max_entries == 0 {
+            return Err(ContractError::MaxEntriesZero {}.into());
+        }
+        pool_info.max_entries = max_entries;
+    }
+    if let Some(slash_policy) = param.slash_policy {
+        pool_info.slash_policy = slash_policy;
+    }
+    if let Some(slash_unstake_haircut) = param.slash_unstake_haircut {
+        pool_info.slash_unstake_haircut = slash_unstake_haircut;
+    }
+    if let Some(auto_redelegate_inactive) = param.auto_redelegate_inactive {
+        pool_info.auto_redelegate_inactive = auto_redelegate_inactive;
+    }
+    if let Some(liquidity_buffer_target) = param.liquidity_buffer_target {
+        pool_info.liquidity_buffer_target = liquidity_buffer_target;
+    }
+    if let Some(liquidity_buffer_ratio) = param.liquidity_buffer_ratio {
+        if liquidity_buffer_ratio > CAL_BASE {
+            return Err(ContractError::LiquidityBufferRatioOverLimit {}.into());
+        }
+        pool_info.liquidity_buffer_ratio = liquidity_buffer_ratio;
+    }
+    if let Some(instant_unstake_fee_min) = param.instant_unstake_fee_min {
+        pool_info.instant_unstake_fee_min = instant_unstake_fee_min;
+    }
+    if let Some(instant_unstake_fee_max) = param.instant_unstake_fee_max {
+        pool_info.instant_unstake_fee_max = instant_unstake_fee_max;
+    }
+    if pool_info.instant_unstake_fee_min > pool_info.instant_unstake_fee_max
+        || pool_info.instant_unstake_fee_max > CAL_BASE
+    {
+        return Err(ContractError::InstantUnstakeFeeInvalid {}.into());
+    }
+    if let Some(unstake_nft) = param.unstake_nft {
+        // existing receipts are bound to the first contract
+        if pool_info.unstake_nft.is_some() {
+            return Err(ContractError::UnstakeNftAlreadySet {}.into());
+        }
+        pool_info.unstake_nft = Some(unstake_nft);
+    }
+    if let Some(withdraw_for_tip) = param.withdraw_for_tip {
+        if withdraw_for_tip > MAX_WITHDRAW_FOR_TIP {
+            return Err(ContractError::WithdrawForTipOverLimit {}.into());
+        }
+        pool_info.withdraw_for_tip = withdraw_for_tip;
+    }
+    if let Some(host_transfer_channel_id) = param.host_transfer_channel_id {
+        pool_info.host_transfer_channel_id = host_transfer_channel_id;
+    }
+    if let Some(tx_timeouts) = param.tx_timeouts {
+        let mut tx_types = vec![];
+        for tx_timeout in tx_timeouts.iter() {
+            if tx_timeout.timeout_seconds < MIN_TX_TIMEOUT_SECONDS
+                || tx_timeout.timeout_seconds > MAX_TX_TIMEOUT_SECONDS
+            {
+                return Err(ContractError::TxTimeoutOutOfRange {}.into());
+            }
+            if tx_types.contains(&tx_timeout.tx_type) {
+                return Err(ContractError::DuplicateTxTimeout {}.into());
+            }
+            tx_types.push(tx_timeout.tx_type.clone());
+        }
+        pool_info.tx_timeouts = tx_timeouts;
+    }
+    if let Some(extra_reward_route) = param.extra_reward_route {
+        match &extra_reward_route {
+            ExtraRewardRoute::Keep => {}
+            ExtraRewardRoute::Treasury { receiver } => {
+                if receiver.is_empty() {
+                    return Err(ContractError::ExtraRewardRouteNotMatch {}.into());
+                }
+            }
+            ExtraRewardRoute::Swap { contract, .. } => {
+                if contract.is_empty() {
+                    return Err(ContractError::ExtraRewardRouteNotMatch {}.into());
+                }
+            }
+        }
+        pool_info.extra_reward_route = extra_reward_route;
+    }
+
+    if let Some(fee_mode) = param.fee_mode {
+        match &fee_mode {
+            FeeMode::LsdToken => {}
+            FeeMode::NativeHostChain { receiver } => {
+                if receiver.is_empty() {
+                    return Err(ContractError::FeeModeNotMatch {}.into());
+                }
+            }
+            FeeMode::NativeNeutron => {}
+        }
+        pool_info.fee_mode = fee_mode;
+    }
+    if pool_info.fee_mode == FeeMode::NativeNeutron && pool_info.host_transfer_channel_id.is_empty()
+    {
+        return Err(ContractError::HostTransferChannelNotSet {}.into());
+    }
+
+    let mut msgs = vec![];
+    if let Some(extra_reward_denoms) = param.extra_reward_denoms {
+        if extra_reward_denoms.len() > MAX_EXTRA_REWARD_DENOMS {
+            return Err(ContractError::ExtraRewardDenomsOverLimit {}.into());
+        }
+        let mut denoms = vec![];
+        for denom in extra_reward_denoms.iter() {
+            if denom.is_empty() || *denom == pool_info.remote_denom || denoms.contains(denom) {
+                return Err(ContractError::ExtraRewardDenomNotMatch(denom.clone()).into());
+            }
+            denoms.push(denom.clone());
+        }
+        pool_info.extra_reward_denoms = extra_reward_denoms;
+
+        // track the new denoms on the withdraw ica balances icq
+        let (_, withdraw_ica_info, _) =
+            INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+        let query_id = get_query_id(
+            deps.as_ref(),
+            withdraw_ica_info.ica_addr.clone(),
+            QueryKind::Balances,
+        )?;
+        msgs.push(NeutronMsg::update_interchain_query(
+            query_id,
+            Some(withdraw_balances_kv_keys(
+                &withdraw_ica_info.ica_addr,
+                &pool_info,
+            )?),
+            None,
+            None,
+        )?);
+    }
 
     POOLS.save(deps.storage, param.pool_addr.clone(), &pool_info)?;
 
-    Ok(Response::default())
+    Ok(Response::default().add_messages(msgs))
 }
@@ -1,15 +1,17 @@
-use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
 };
 
+use crate::execute_admin_transfer::pending_stack_admin;
 use crate::state::STACK;
 use crate::{error_conversion::ContractError, msg::ConfigStackParams};
 
 pub fn execute_config_stack(
     deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     param: ConfigStackParams,
 ) -> NeutronResult<Response<NeutronMsg>> {
@@ -24,6 +26,9 @@ pub fn execute_config_stack(
         stack.stack_fee_commission = stack_fee_commission;
     }
     if let Some(new_admin) = param.new_admin {
+        if pending_stack_admin(deps.storage, &env)?.is_some() {
+            return Err(ContractError::PendingAdminExists {}.into());
+        }
         stack.admin = new_admin;
     }
     if let Some(lsd_token_code_id) = param.lsd_token_code_id {
@@ -34,6 +39,9 @@ pub fn execute_config_stack(
             stack.pools.push(add_entrusted_pool);
         }
     }
+    if let Some(remove_entrusted_pool) = param.remove_entrusted_pool {
+        stack.pools.retain(|pool| *pool != remove_entrusted_pool);
+    }
 
     STACK.save(deps.storage, &stack)?;
 
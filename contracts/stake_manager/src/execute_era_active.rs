@@ -1,7 +1,10 @@
+use crate::execute_roles::check_era_operator;
+use crate::slashing::{apply_unstake_haircut, detect_validator_slashes};
 use crate::state::{
     EraStatus::{ActiveEnded, RebondEnded},
-    STACK,
+    PoolInfo, SlashEvent, SlashPolicy, SLASH_EVENTS, STACK,
 };
+use crate::validator_health::{redelegate_inactive_msg, update_inactive_validators};
 use crate::{error_conversion::ContractError, state::POOLS};
 use crate::{helper::get_update_pool_icq_msgs, state::ERA_RATE};
 use crate::{helper::CAL_BASE, query::query_delegation_by_addr};
@@ -10,7 +13,7 @@ use crate::{
     state::TOTAL_STACK_FEE,
 };
 use core::ops::{Mul, Sub};
-use cosmwasm_std::{to_json_binary, DepsMut, Response, Uint128, WasmMsg};
+use cosmwasm_std::{to_json_binary, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
@@ -18,10 +21,13 @@ use neutron_sdk::{
 use std::ops::{Add, Div};
 
 pub fn execute_era_active(
-    deps: DepsMut<NeutronQuery>,
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
     pool_addr: String,
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    check_era_operator(deps.storage, &pool_info, pool_addr.clone(), &info.sender)?;
     // check era state
     if pool_info.status != RebondEnded {
         return Err(ContractError::StatusNotAllow {}.into());
@@ -42,26 +48,72 @@ pub fn execute_era_active(
         amount: Uint128::zero(),
     };
 
-    match delegations_result {
+    let delegations = match delegations_result {
         Ok(delegations_resp) => {
             if delegations_resp.last_submitted_local_height
                 <= pool_info.era_snapshot.last_step_height
             {
                 return Err(ContractError::DelegationSubmissionHeight {}.into());
             }
-            for delegation in delegations_resp.delegations {
+            for delegation in delegations_resp.delegations.iter() {
                 total_amount.amount = total_amount.amount.add(delegation.amount.amount);
             }
+            delegations_resp.delegations
         }
         Err(_) => {
             return Err(ContractError::DelegationsNotExist {}.into());
         }
+    };
+
+    let total_delegated = total_amount.amount;
+    total_amount.amount = total_amount
+        .amount
+        .add(pool_info.era_snapshot.liquidity_buffer);
+
+    let validator_slashes =
+        detect_validator_slashes(deps.storage, pool_addr.clone(), &delegations)?;
+    let slash_loss = validator_slashes
+        .iter()
+        .fold(Uint128::zero(), |acc, slash| acc.add(slash.loss));
+    let acknowledged = SLASH_EVENTS
+        .may_load(deps.storage, (pool_addr.clone(), pool_info.era))?
+        .map(|slash_event| slash_event.acknowledged)
+        .unwrap_or(false);
+    let mut slash_event = SlashEvent {
+        era: pool_info.era,
+        loss: slash_loss,
+        validators: validator_slashes,
+        policy: pool_info.slash_policy.clone(),
+        unstake_haircut: Uint128::zero(),
+        rate_before: pool_info.rate,
+        rate_after: pool_info.rate,
+        acknowledged,
+    };
+    if !slash_loss.is_zero() && pool_info.slash_policy == SlashPolicy::PauseAndWait && !acknowledged
+    {
+        // keep the era in rebond ended until the admin acknowledges the loss
+        pool_info.paused = true;
+        POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
+        SLASH_EVENTS.save(
+            deps.storage,
+            (pool_addr.clone(), pool_info.era),
+            &slash_event,
+        )?;
+
+        return Ok(Response::new()
+            .add_attribute("action", "era_active")
+            .add_attribute("pool", pool_addr)
+            .add_attribute("era", pool_info.era.to_string())
+            .add_attribute("slash_loss", slash_loss)
+            .add_attribute("paused", "true"));
     }
 
     let stack_info = STACK.load(deps.storage)?;
-    // calculate protocol fee
-    let (platform_fee, stack_fee) = if total_amount.amount > pool_info.era_snapshot.active {
-        let reward = total_amount.amount.sub(pool_info.era_snapshot.active);
+    // calculate protocol fee, a platform fee paid natively at rebond left the pool but counts as reward
+    let native_platform_fee = pool_info.era_snapshot.native_platform_fee;
+    let total_with_native_fee = total_amount.amount.add(native_platform_fee);
+    let (mut platform_fee, stack_fee) = if total_with_native_fee > pool_info.era_snapshot.active {
+        let reward = total_with_native_fee.sub(pool_info.era_snapshot.active);
         let platform_fee_raw = reward
             .mul(pool_info.platform_fee_commission)
             .div(pool_info.rate);
@@ -73,8 +125,28 @@ pub fn execute_era_active(
     } else {
         (Uint128::zero(), Uint128::zero())
     };
+    // a native fee mode falls back to minting when no native fee was paid at rebond
+    if !native_platform_fee.is_zero() {
+        platform_fee = Uint128::zero();
+        pool_info.total_native_platform_fee =
+            pool_info.total_native_platform_fee.add(native_platform_fee);
+    }
 
-    let cal_temp = pool_info.active.add(total_amount.amount);
+    // the part of the loss cut from the unbonding unstakes is not borne by the stakers again
+    if !slash_loss.is_zero() && pool_info.slash_unstake_haircut {
+        slash_event.unstake_haircut = apply_unstake_haircut(
+            deps.storage,
+            &pool_info,
+            pool_addr.clone(),
+            slash_loss,
+            total_delegated,
+        )?;
+    }
+
+    let cal_temp = pool_info
+        .active
+        .add(total_amount.amount)
+        .add(slash_event.unstake_haircut);
     let mut new_active = if cal_temp > pool_info.era_snapshot.active {
         cal_temp.sub(pool_info.era_snapshot.active)
     } else {
@@ -93,24 +165,7 @@ pub fn execute_era_active(
         CAL_BASE
     };
 
-    if !pool_info.rate_change_limit.is_zero() {
-        let rate_change = if pool_info.rate > new_rate {
-            pool_info
-                .rate
-                .sub(new_rate)
-                .mul(CAL_BASE)
-                .div(pool_info.rate)
-        } else {
-            new_rate
-                .sub(pool_info.rate)
-                .mul(CAL_BASE)
-                .div(pool_info.rate)
-        };
-
-        if rate_change > pool_info.rate_change_limit {
-            return Err(ContractError::RateChangeOverLimit {}.into());
-        }
-    }
+    check_rate_change_limit(&pool_info, new_rate, &slash_event)?;
 
     // Solve first stake calculation accuracy
     if pool_info.rate == DEFAULT_RATE
@@ -126,6 +181,17 @@ pub fn execute_era_active(
     pool_info.active = new_active;
 
     let mut resp = Response::new().add_attribute("new_rate", pool_info.rate);
+    if !slash_loss.is_zero() {
+        slash_event.rate_after = pool_info.rate;
+        SLASH_EVENTS.save(
+            deps.storage,
+            (pool_addr.clone(), pool_info.era),
+            &slash_event,
+        )?;
+        resp = resp
+            .add_attribute("slash_loss", slash_loss)
+            .add_attribute("unstake_haircut", slash_event.unstake_haircut);
+    }
     if !platform_fee.is_zero() {
         let msg = WasmMsg::Execute {
             contract_addr: pool_info.lsd_token.to_string(),
@@ -160,6 +226,22 @@ pub fn execute_era_active(
         TOTAL_STACK_FEE.save(deps.storage, pool_addr.clone(), &total_stack_fee)?;
     }
 
+    // validators jailed or out of the active set are excluded from the next era
+    let status_events =
+        update_inactive_validators(deps.branch(), pool_addr.clone(), &mut pool_info)?;
+    let (redelegate_submsg, redelegate_events) = redelegate_inactive_msg(
+        deps.branch(),
+        &env,
+        &info,
+        pool_addr.clone(),
+        &mut pool_info,
+        &delegations,
+    )?;
+    resp = resp.add_events(status_events).add_events(redelegate_events);
+    if let Some(submsg) = redelegate_submsg {
+        resp = resp.add_submessage(submsg);
+    }
+
     POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
     STACK.save(deps.storage, &stack_info)?;
     ERA_RATE.save(
@@ -182,3 +264,133 @@ pub fn execute_era_active(
         .add_attribute("era", pool_info.era.to_string())
         .add_attribute("rate", new_rate))
 }
+
+// a socialized slash or one the admin acknowledged may move the rate over the limit
+fn check_rate_change_limit(
+    pool_info: &PoolInfo,
+    new_rate: Uint128,
+    slash_event: &SlashEvent,
+) -> NeutronResult<()> {
+    if pool_info.rate_change_limit.is_zero()
+        || (!slash_event.loss.is_zero()
+            && (slash_event.policy == SlashPolicy::Socialize || slash_event.acknowledged))
+    {
+        return Ok(());
+    }
+
+    let rate_change = if pool_info.rate > new_rate {
+        pool_info
+            .rate
+            .sub(new_rate)
+            .mul(CAL_BASE)
+            .div(pool_info.rate)
+    } else {
+        new_rate
+            .sub(pool_info.rate)
+            .mul(CAL_BASE)
+            .div(pool_info.rate)
+    };
+    if rate_change > pool_info.rate_change_limit {
+        return Err(ContractError::RateChangeOverLimit {}.into());
+    }
+
+    Ok(())
+}
+
+// accept the loss of a slash that paused the pool in era active and resume the era
+pub fn execute_acknowledge_slash(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    pool_addr: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    if info.sender != pool_info.admin {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+    if pool_info.status != RebondEnded {
+        return Err(ContractError::StatusNotAllow {}.into());
+    }
+
+    let mut slash_event =
+        match SLASH_EVENTS.may_load(deps.storage, (pool_addr.clone(), pool_info.era))? {
+            Some(slash_event) if !slash_event.acknowledged => slash_event,
+            _ => return Err(ContractError::SlashNotPending {}.into()),
+        };
+    slash_event.acknowledged = true;
+    pool_info.paused = false;
+
+    SLASH_EVENTS.save(
+        deps.storage,
+        (pool_addr.clone(), pool_info.era),
+        &slash_event,
+    )?;
+    POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "acknowledge_slash")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("era", pool_info.era.to_string())
+        .add_attribute("slash_loss", slash_event.loss))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::tests::BASELINE_POOL_INFO;
+    use cosmwasm_std::testing::{mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_json, OwnedDeps};
+    use std::marker::PhantomData;
+
+    const POOL_ADDR: &str = "pool_ica_addr";
+
+    #[test]
+    fn acknowledge_slash_resumes_paused_pool() -> NeutronResult<()> {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: MockQuerier::<NeutronQuery>::new(&[]),
+            custom_query_type: PhantomData,
+        };
+        let mut pool_info: PoolInfo = from_json(BASELINE_POOL_INFO)?;
+        pool_info.status = RebondEnded;
+        pool_info.paused = true;
+        pool_info.slash_policy = SlashPolicy::PauseAndWait;
+        POOLS.save(deps.as_mut().storage, POOL_ADDR.to_string(), &pool_info)?;
+
+        let acknowledge = |deps: DepsMut<NeutronQuery>, sender: &str| {
+            execute_acknowledge_slash(deps, mock_info(sender, &[]), POOL_ADDR.to_string())
+        };
+        assert!(acknowledge(deps.as_mut(), "admin").is_err());
+
+        let slash_event = SlashEvent {
+            era: pool_info.era,
+            loss: Uint128::new(10),
+            validators: vec![],
+            policy: SlashPolicy::PauseAndWait,
+            unstake_haircut: Uint128::zero(),
+            rate_before: pool_info.rate,
+            rate_after: pool_info.rate,
+            acknowledged: false,
+        };
+        SLASH_EVENTS.save(
+            deps.as_mut().storage,
+            (POOL_ADDR.to_string(), pool_info.era),
+            &slash_event,
+        )?;
+        assert!(acknowledge(deps.as_mut(), "user").is_err());
+
+        // the slash lowers the rate by 5% against a 1% limit
+        pool_info.rate_change_limit = Uint128::new(10_000);
+        let new_rate = Uint128::new(950_000);
+        assert!(check_rate_change_limit(&pool_info, new_rate, &slash_event).is_err());
+
+        acknowledge(deps.as_mut(), "admin")?;
+        assert!(!POOLS.load(&deps.storage, POOL_ADDR.to_string())?.paused);
+        let slash_event =
+            SLASH_EVENTS.load(&deps.storage, (POOL_ADDR.to_string(), pool_info.era))?;
+        assert!(slash_event.acknowledged);
+        check_rate_change_limit(&pool_info, new_rate, &slash_event)?;
+        assert!(acknowledge(deps.as_mut(), "admin").is_err());
+        Ok(())
+    }
+}
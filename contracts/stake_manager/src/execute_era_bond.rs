@@ -1,9 +1,13 @@
-use crate::helper::{check_ibc_fee, STAKE_SPLIT_THRESHOLD};
+use crate::execute_roles::check_era_operator;
+use crate::helper::{check_ibc_fee, check_ica_channel_open, immature_unbond_times};
+use crate::slashing::save_expected_delegations;
 use crate::state::EraStatus::{BondEnded, BondStarted, EraUpdateEnded};
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::state::{SudoPayload, TxType, INFO_OF_ICA_ID, POOLS, VALIDATORS_UNBONDS_TIME};
 use crate::tx_callback::msg_with_sudo_callback;
+use crate::validator_selection::{allocate_delegate_amount, sort_delegations_for_unbond};
 use crate::{error_conversion::ContractError, helper::gen_delegation_txs};
-use crate::{helper::DEFAULT_TIMEOUT_SECONDS, query::query_delegation_by_addr};
+use crate::{helper::tx_timeout_seconds, query::query_delegation_by_addr};
 use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate;
 use cosmos_sdk_proto::cosmos::{
     base::v1beta1::Coin, distribution::v1beta1::MsgWithdrawDelegatorReward,
@@ -16,10 +20,7 @@ use neutron_sdk::{
     NeutronResult,
 };
 use std::vec;
-use std::{
-    collections::HashSet,
-    ops::{Div, Mul, Sub},
-};
+use std::{collections::HashSet, ops::Sub};
 
 #[derive(Clone, Debug)]
 struct ValidatorUnbondInfo {
@@ -32,56 +33,59 @@ pub fn execute_era_bond(
     env: Env,
     info: MessageInfo,
     pool_addr: String,
-    select_vals: Vec<String>,
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    check_era_operator(deps.storage, &pool_info, pool_addr.clone(), &info.sender)?;
 
     // check era state
     if pool_info.status != EraUpdateEnded {
         return Err(ContractError::StatusNotAllow {}.into());
     }
 
+    let delegations = query_delegation_by_addr(
+        deps.as_ref(),
+        pool_addr.clone(),
+        pool_info.sdk_greater_or_equal_v047,
+    )?;
+    if delegations.last_submitted_local_height <= pool_info.era_snapshot.last_step_height {
+        return Err(ContractError::DelegationSubmissionHeight {}.into());
+    }
+    let delegating_vals: Vec<String> = delegations
+        .delegations
+        .iter()
+        .filter(|delegation| !delegation.amount.amount.is_zero())
+        .map(|delegation| delegation.validator.clone())
+        .collect();
+
     let mut msgs = vec![];
+    let mut op_validators = vec![];
+    let mut delegated = vec![];
+    let mut undelegated = vec![];
 
-    let mut msg_str = "".to_string();
+    let mut unbond_validators = vec![];
     if pool_info.era_snapshot.unbond >= pool_info.era_snapshot.bond {
         let unbond_amount = pool_info
             .era_snapshot
             .unbond
             .sub(pool_info.era_snapshot.bond);
 
-        let delegations = query_delegation_by_addr(
-            deps.as_ref(),
-            pool_addr.clone(),
-            pool_info.sdk_greater_or_equal_v047,
-        )?;
-        if delegations.last_submitted_local_height <= pool_info.era_snapshot.last_step_height {
-            return Err(ContractError::DelegationSubmissionHeight {}.into());
-        }
-        let delegating_vals: Vec<String> = delegations
-            .delegations
-            .iter()
-            .map(|delegation| delegation.validator.clone())
-            .collect();
-
-        let mut op_validators = vec![];
         if unbond_amount.u128() > 0 {
             let unbond_infos = allocate_unbond_amount(
                 deps.branch(),
                 env.block.time.seconds(),
-                &delegations.delegations,
+                &sort_delegations_for_unbond(&pool_info, &delegations.delegations, unbond_amount),
                 unbond_amount,
                 pool_info.unbonding_period * pool_info.era_seconds,
+                pool_info.max_entries,
             )?;
             if unbond_infos.is_empty() {
                 return Err(ContractError::ValidatorForUnbondNotEnough {}.into());
             }
-            let unbond_validators: Vec<String> =
-                unbond_infos.iter().map(|u| u.validator.clone()).collect();
-            msg_str = unbond_validators.join("_");
+            unbond_validators = unbond_infos.iter().map(|u| u.validator.clone()).collect();
 
             for info in unbond_infos {
                 op_validators.push(info.validator.clone());
+                undelegated.push((info.validator.clone(), info.unbond_amount));
 
                 // add submessage to unstake
                 let delegate_msg = MsgUndelegate {
@@ -107,110 +111,71 @@ pub fn execute_era_bond(
                 msgs.push(any_msg);
             }
         }
-        // Check whether the delegator-validator needs to manually withdraw
-        if op_validators.len() != delegating_vals.len() {
-            // Find the difference between delegation validator_addrs and op_validators
-            let pool_validators: HashSet<_> = delegating_vals.into_iter().collect();
-            let op_validators_set: HashSet<_> = op_validators.into_iter().collect();
-
-            // Find the difference
-            let difference: HashSet<_> = pool_validators.difference(&op_validators_set).collect();
-
-            // Convert the difference back to Vec
-            let difference_vec: Vec<_> = difference.into_iter().collect();
-            for validator_addr in difference_vec {
-                // Create a MsgWithdrawDelegatorReward message
-                let withdraw_msg = MsgWithdrawDelegatorReward {
-                    delegator_address: pool_addr.clone(),
-                    validator_address: validator_addr.clone(),
-                };
-
-                // Serialize the MsgWithdrawDelegatorReward message
-                let mut buf = Vec::new();
-                buf.reserve(withdraw_msg.encoded_len());
-
-                if let Err(e) = withdraw_msg.encode(&mut buf) {
-                    return Err(ContractError::EncodeError(e.to_string()).into());
-                }
-
-                // Put the serialized MsgWithdrawDelegatorReward message to a types.Any protobuf message
-                let any_msg = ProtobufAny {
-                    type_url: "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward".to_string(),
-                    value: Binary::from(buf),
-                };
-
-                // Form the neutron SubmitTx message containing the binary MsgWithdrawDelegatorReward message
-                msgs.push(any_msg);
-            }
-        }
     } else {
         let stake_amount = pool_info.era_snapshot.bond - pool_info.era_snapshot.unbond;
-        let validator_count = select_vals.len() as u128;
-        if validator_count == 0 {
-            return Err(ContractError::ValidatorsEmpty {}.into());
+        let delegate_infos =
+            allocate_delegate_amount(&mut pool_info, &delegations.delegations, stake_amount)?;
+
+        for info in delegate_infos {
+            op_validators.push(info.validator.clone());
+            delegated.push((info.validator.clone(), info.delegate_amount));
+
+            msgs.push(gen_delegation_txs(
+                pool_addr.clone(),
+                info.validator,
+                pool_info.remote_denom.clone(),
+                info.delegate_amount,
+            ));
         }
-        if stake_amount < STAKE_SPLIT_THRESHOLD {
-            for (index, validator_addr) in select_vals.iter().enumerate() {
-                if !pool_info.validator_addrs.contains(validator_addr) {
-                    return Err(ContractError::ValidatorNotSupport {}.into());
-                }
-                if index == 0 {
-                    msgs.push(gen_delegation_txs(
-                        pool_addr.clone(),
-                        validator_addr.clone(),
-                        pool_info.remote_denom.clone(),
-                        stake_amount,
-                    ));
-                } else {
-                    let withdraw_msg = MsgWithdrawDelegatorReward {
-                        delegator_address: pool_addr.clone(),
-                        validator_address: validator_addr.clone(),
-                    };
-
-                    let mut buf = Vec::new();
-                    buf.reserve(withdraw_msg.encoded_len());
-
-                    if let Err(e) = withdraw_msg.encode(&mut buf) {
-                        return Err(ContractError::EncodeError(e.to_string()).into());
-                    }
-
-                    msgs.push(ProtobufAny {
-                        type_url: "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward"
-                            .to_string(),
-                        value: Binary::from(buf),
-                    });
-                }
-            }
-        } else {
-            let amount_per_validator = stake_amount.div(Uint128::from(validator_count));
-            let remainder =
-                stake_amount.sub(amount_per_validator.mul(Uint128::new(validator_count)));
-
-            for (index, validator_addr) in select_vals.iter().enumerate() {
-                if !pool_info.validator_addrs.contains(validator_addr) {
-                    return Err(ContractError::ValidatorNotSupport {}.into());
-                }
-
-                let mut amount_for_this_validator = amount_per_validator;
+    }
 
-                // Add the remainder to the first validator
-                if index == 0 {
-                    amount_for_this_validator += remainder;
-                }
+    // Check whether the delegator-validator needs to manually withdraw
+    if op_validators.len() != delegating_vals.len() {
+        // Find the difference between delegation validator_addrs and op_validators
+        let pool_validators: HashSet<_> = delegating_vals.into_iter().collect();
+        let op_validators_set: HashSet<_> = op_validators.into_iter().collect();
+
+        // Find the difference
+        let difference: HashSet<_> = pool_validators.difference(&op_validators_set).collect();
+
+        // Convert the difference back to Vec
+        let difference_vec: Vec<_> = difference.into_iter().collect();
+        for validator_addr in difference_vec {
+            // Create a MsgWithdrawDelegatorReward message
+            let withdraw_msg = MsgWithdrawDelegatorReward {
+                delegator_address: pool_addr.clone(),
+                validator_address: validator_addr.clone(),
+            };
 
-                let any_msg = gen_delegation_txs(
-                    pool_addr.clone(),
-                    validator_addr.clone(),
-                    pool_info.remote_denom.clone(),
-                    amount_for_this_validator,
-                );
+            // Serialize the MsgWithdrawDelegatorReward message
+            let mut buf = Vec::new();
+            buf.reserve(withdraw_msg.encoded_len());
 
-                msgs.push(any_msg);
+            if let Err(e) = withdraw_msg.encode(&mut buf) {
+                return Err(ContractError::EncodeError(e.to_string()).into());
             }
+
+            // Put the serialized MsgWithdrawDelegatorReward message to a types.Any protobuf message
+            let any_msg = ProtobufAny {
+                type_url: "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward".to_string(),
+                value: Binary::from(buf),
+            };
+
+            // Form the neutron SubmitTx message containing the binary MsgWithdrawDelegatorReward message
+            msgs.push(any_msg);
         }
     }
 
+    save_expected_delegations(
+        deps.storage,
+        pool_addr.clone(),
+        &delegations.delegations,
+        &delegated,
+        &undelegated,
+    )?;
+
     let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    check_ica_channel_open(&pool_ica_info)?;
 
     let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
     let cosmos_msg = NeutronMsg::submit_tx(
@@ -218,7 +183,7 @@ pub fn execute_era_bond(
         pool_info.ica_id.clone(),
         msgs,
         "".to_string(),
-        DEFAULT_TIMEOUT_SECONDS,
+        tx_timeout_seconds(&pool_info, &TxType::EraBond),
         ibc_fee,
     );
 
@@ -227,8 +192,8 @@ pub fn execute_era_bond(
         cosmos_msg,
         SudoPayload {
             port_id: pool_ica_info.ctrl_port_id,
-            // the acknowledgement later
-            message: msg_str,
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::EraBond { unbond_validators },
             pool_addr: pool_addr.clone(),
             tx_type: TxType::EraBond,
         },
@@ -246,44 +211,31 @@ fn allocate_unbond_amount(
     delegations: &[Delegation],
     unbond_amount: Uint128,
     unbonding_period_seconds: u64,
+    max_entries: u64,
 ) -> NeutronResult<Vec<ValidatorUnbondInfo>> {
     let mut unbond_infos: Vec<ValidatorUnbondInfo> = Vec::new();
     let mut remaining_unbond = unbond_amount;
 
-    // Sort the delegations by amount in descending order
-    let mut sorted_delegations = delegations.to_vec();
-    sorted_delegations.sort_by(|a, b| b.amount.amount.cmp(&a.amount.amount));
-    for delegation in sorted_delegations.iter() {
+    // delegations are sorted by how far they are above their target weight
+    for delegation in delegations.iter() {
         if remaining_unbond.is_zero() {
             break;
         }
 
-        // clear timestamps
-        if let Some(mut timestamps) = VALIDATORS_UNBONDS_TIME.may_load(
-            deps.storage,
-            (
-                delegation.delegator.to_string(),
-                delegation.validator.clone(),
-            ),
-        )? {
-            if !timestamps.is_empty() {
-                let oldest_record_time = timestamps[0];
-                if current_time > oldest_record_time + unbonding_period_seconds {
-                    timestamps.remove(0);
-
-                    VALIDATORS_UNBONDS_TIME.save(
-                        deps.storage,
-                        (
-                            delegation.delegator.to_string(),
-                            delegation.validator.clone(),
-                        ),
-                        &timestamps,
-                    )?;
-                }
+        // clear all mature timestamps
+        let key = (
+            delegation.delegator.to_string(),
+            delegation.validator.clone(),
+        );
+        if let Some(timestamps) = VALIDATORS_UNBONDS_TIME.may_load(deps.storage, key.clone())? {
+            let immature_timestamps =
+                immature_unbond_times(timestamps.clone(), current_time, unbonding_period_seconds);
+            if immature_timestamps.len() != timestamps.len() {
+                VALIDATORS_UNBONDS_TIME.save(deps.storage, key, &immature_timestamps)?;
+            }
 
-                if timestamps.len() >= 7 {
-                    continue;
-                }
+            if immature_timestamps.len() as u64 >= max_entries {
+                continue;
             }
         }
 
@@ -313,8 +265,10 @@ pub fn sudo_era_bond_callback(
     payload: SudoPayload,
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
-    if payload.message.len() > 0 {
-        let unbond_validators: Vec<String> = payload.message.split("_").map(String::from).collect();
+    let SudoData::EraBond { unbond_validators } = payload.data else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
+    if !unbond_validators.is_empty() {
         let timestamp = env.block.time.seconds();
         for unbond_validator in unbond_validators {
             let timestamps_op = VALIDATORS_UNBONDS_TIME.may_load(
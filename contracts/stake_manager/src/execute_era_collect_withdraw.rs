@@ -1,10 +1,15 @@
-use crate::helper::{check_ibc_fee, gen_msg_send, get_withdraw_ica_id};
+use crate::execute_roles::check_era_operator;
+use crate::helper::{
+    check_ibc_fee, check_ica_channel_open, gen_msg_execute_contract, gen_msg_send,
+    get_withdraw_ica_id,
+};
 use crate::query::query_balance_by_addr;
 use crate::state::EraStatus::{BondEnded, WithdrawEnded, WithdrawStarted};
-use crate::state::{SudoPayload, TxType, INFO_OF_ICA_ID, POOLS};
+use crate::state::{ExtraRewardRoute, SudoPayload, TxType, INFO_OF_ICA_ID, POOLS};
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::tx_callback::msg_with_sudo_callback;
-use crate::{error_conversion::ContractError, helper::DEFAULT_TIMEOUT_SECONDS};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+use crate::{error_conversion::ContractError, helper::tx_timeout_seconds};
+use cosmwasm_std::{Coin, DepsMut, Env, MessageInfo, Response, Uint128};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
@@ -16,6 +21,7 @@ pub fn execute_era_collect_withdraw(
     pool_addr: String,
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    check_era_operator(deps.storage, &pool_info, pool_addr.clone(), &info.sender)?;
 
     // check era state
     if pool_info.status != BondEnded {
@@ -24,6 +30,7 @@ pub fn execute_era_collect_withdraw(
     pool_info.status = WithdrawStarted;
 
     let (_, withdraw_ica_info, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    check_ica_channel_open(&withdraw_ica_info)?;
 
     // check withdraw address balance and send it to the pool
     let withdraw_balances_result = query_balance_by_addr(
@@ -33,6 +40,7 @@ pub fn execute_era_collect_withdraw(
     );
 
     let mut withdraw_amount = Uint128::zero();
+    let mut extra_rewards: Vec<Coin> = vec![];
     if let Ok(balance_response) = withdraw_balances_result {
         if balance_response.last_submitted_local_height <= pool_info.era_snapshot.last_step_height {
             return Err(ContractError::WithdrawAddrBalanceSubmissionHeight {}.into());
@@ -47,28 +55,66 @@ pub fn execute_era_collect_withdraw(
                 .map(|c| c.amount)
                 .unwrap_or(Uint128::zero());
         }
+        if pool_info.extra_reward_route != ExtraRewardRoute::Keep {
+            extra_rewards = balance_response
+                .balances
+                .coins
+                .into_iter()
+                .filter(|c| !c.amount.is_zero() && pool_info.extra_reward_denoms.contains(&c.denom))
+                .collect();
+            // funds of a contract call must be sorted
+            extra_rewards.sort_by(|a, b| a.denom.cmp(&b.denom));
+        }
     }
 
     // leave gas
-    if withdraw_amount.is_zero() {
+    if withdraw_amount.is_zero() && extra_rewards.is_empty() {
         pool_info.status = WithdrawEnded;
         POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
 
         return Ok(Response::default());
     }
 
-    let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
-    let cosmos_msg = NeutronMsg::submit_tx(
-        withdraw_ica_info.ctrl_connection_id.clone(),
-        get_withdraw_ica_id(pool_info.ica_id.clone()),
-        vec![gen_msg_send(
+    let mut msgs = vec![];
+    if !withdraw_amount.is_zero() {
+        msgs.push(gen_msg_send(
             withdraw_ica_info.ica_addr.clone(),
             pool_addr.clone(),
             pool_info.remote_denom.clone(),
             withdraw_amount.to_string(),
-        )?],
+        )?);
+    }
+    if !extra_rewards.is_empty() {
+        match pool_info.extra_reward_route.clone() {
+            ExtraRewardRoute::Keep => {}
+            ExtraRewardRoute::Treasury { receiver } => {
+                for coin in extra_rewards {
+                    msgs.push(gen_msg_send(
+                        withdraw_ica_info.ica_addr.clone(),
+                        receiver.clone(),
+                        coin.denom,
+                        coin.amount.to_string(),
+                    )?);
+                }
+            }
+            ExtraRewardRoute::Swap { contract, msg } => {
+                msgs.push(gen_msg_execute_contract(
+                    withdraw_ica_info.ica_addr.clone(),
+                    contract,
+                    msg,
+                    extra_rewards,
+                ));
+            }
+        }
+    }
+
+    let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
+    let cosmos_msg = NeutronMsg::submit_tx(
+        withdraw_ica_info.ctrl_connection_id.clone(),
+        get_withdraw_ica_id(pool_info.ica_id.clone()),
+        msgs,
         "".to_string(),
-        DEFAULT_TIMEOUT_SECONDS,
+        tx_timeout_seconds(&pool_info, &TxType::EraCollectWithdraw),
         ibc_fee,
     );
 
@@ -77,7 +123,8 @@ pub fn execute_era_collect_withdraw(
         cosmos_msg,
         SudoPayload {
             port_id: withdraw_ica_info.ctrl_port_id,
-            message: "".to_string(),
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::Empty {},
             pool_addr: pool_addr.clone(),
             tx_type: TxType::EraCollectWithdraw,
         },
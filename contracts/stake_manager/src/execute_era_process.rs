@@ -0,0 +1,211 @@
+use crate::error_conversion::ContractError;
+use crate::execute_era_active::execute_era_active;
+use crate::execute_era_bond::execute_era_bond;
+use crate::execute_era_collect_withdraw::execute_era_collect_withdraw;
+use crate::execute_era_rebond::execute_era_rebond;
+use crate::execute_era_update::execute_era_update;
+use crate::execute_roles::check_era_operator;
+use crate::query::{query_balance_by_addr, query_delegation_by_addr};
+use crate::state::{
+    EraProcessResponse, EraProcessStep, EraProcessWait, EraStatus, PoolInfo, QueryKind,
+    ValidatorUpdateStatus, INFO_OF_ICA_ID, POOLS,
+};
+use cosmwasm_std::{to_json_binary, Deps, DepsMut, Env, MessageInfo, Response};
+use neutron_sdk::{
+    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    NeutronResult,
+};
+use std::ops::Div;
+
+pub fn execute_era_process(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    pool_addr: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    check_era_operator(deps.storage, &pool_info, pool_addr.clone(), &info.sender)?;
+
+    let (step, tx_pending) = era_step_of_status(&pool_info.status)?;
+    if tx_pending {
+        return era_process_response(
+            Response::new(),
+            pool_addr,
+            &pool_info,
+            None,
+            Some(EraProcessWait::TxAck),
+        );
+    }
+
+    if let Some(wait) = era_step_wait(deps.as_ref(), &env, &pool_addr, &pool_info, &step)? {
+        return era_process_response(Response::new(), pool_addr, &pool_info, None, Some(wait));
+    }
+
+    let rsp = match step {
+        EraProcessStep::EraUpdate => {
+            execute_era_update(deps.branch(), env, info, pool_addr.clone())?
+        }
+        EraProcessStep::EraBond => execute_era_bond(deps.branch(), env, info, pool_addr.clone())?,
+        EraProcessStep::EraCollectWithdraw => {
+            execute_era_collect_withdraw(deps.branch(), info, pool_addr.clone())?
+        }
+        EraProcessStep::EraRebond => {
+            execute_era_rebond(deps.branch(), env, info, pool_addr.clone())?
+        }
+        EraProcessStep::EraActive => {
+            execute_era_active(deps.branch(), env, info, pool_addr.clone())?
+        }
+    };
+
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    let (_, tx_pending) = era_step_of_status(&pool_info.status)?;
+    let wait = if tx_pending {
+        Some(EraProcessWait::TxAck)
+    } else {
+        None
+    };
+
+    era_process_response(rsp, pool_addr, &pool_info, Some(step), wait)
+}
+
+// returns the step that follows the status and whether an ack is still pending before it
+pub fn era_step_of_status(status: &EraStatus) -> NeutronResult<(EraProcessStep, bool)> {
+    let step = match status {
+        EraStatus::ActiveEnded => (EraProcessStep::EraUpdate, false),
+        EraStatus::EraUpdateStarted => (EraProcessStep::EraBond, true),
+        EraStatus::EraUpdateEnded => (EraProcessStep::EraBond, false),
+        EraStatus::BondStarted => (EraProcessStep::EraCollectWithdraw, true),
+        EraStatus::BondEnded => (EraProcessStep::EraCollectWithdraw, false),
+        EraStatus::WithdrawStarted => (EraProcessStep::EraRebond, true),
+        EraStatus::WithdrawEnded => (EraProcessStep::EraRebond, false),
+        EraStatus::RebondStarted => (EraProcessStep::EraActive, true),
+        EraStatus::RebondEnded => (EraProcessStep::EraActive, false),
+        EraStatus::RegisterEnded | EraStatus::InitStarted | EraStatus::InitFailed => {
+            return Err(ContractError::StatusNotAllow {}.into());
+        }
+    };
+
+    Ok(step)
+}
+
+// checks the preconditions of the step which the keeper can't satisfy by itself
+pub fn era_step_wait(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    pool_addr: &str,
+    pool_info: &PoolInfo,
+    step: &EraProcessStep,
+) -> NeutronResult<Option<EraProcessWait>> {
+    let last_step_height = pool_info.era_snapshot.last_step_height;
+
+    match step {
+        EraProcessStep::EraUpdate => {
+            if pool_info.paused {
+                return Ok(Some(EraProcessWait::PoolPaused));
+            }
+            if pool_info.validator_update_status != ValidatorUpdateStatus::End {
+                return Ok(Some(EraProcessWait::ValidatorUpdate));
+            }
+            let current_era = env
+                .block
+                .time
+                .seconds()
+                .div(pool_info.era_seconds)
+                .saturating_add_signed(pool_info.offset);
+            if current_era <= pool_info.era {
+                return Ok(Some(EraProcessWait::NextEra { current_era }));
+            }
+        }
+        EraProcessStep::EraBond => {
+            let delegations = query_delegation_by_addr(
+                deps,
+                pool_addr.to_string(),
+                pool_info.sdk_greater_or_equal_v047,
+            )?;
+            if delegations.last_submitted_local_height <= last_step_height {
+                return Ok(Some(EraProcessWait::IcqUpdate {
+                    query_kind: QueryKind::Delegations,
+                    last_step_height,
+                    last_submitted_local_height: delegations.last_submitted_local_height,
+                }));
+            }
+        }
+        EraProcessStep::EraCollectWithdraw => {
+            let (_, withdraw_ica_info, _) =
+                INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+            if let Ok(balance_response) = query_balance_by_addr(
+                deps,
+                withdraw_ica_info.ica_addr,
+                pool_info.sdk_greater_or_equal_v047,
+            ) {
+                if balance_response.last_submitted_local_height <= last_step_height {
+                    return Ok(Some(EraProcessWait::IcqUpdate {
+                        query_kind: QueryKind::Balances,
+                        last_step_height,
+                        last_submitted_local_height: balance_response.last_submitted_local_height,
+                    }));
+                }
+            }
+        }
+        EraProcessStep::EraRebond => {
+            if !pool_info.era_snapshot.restake_amount.is_zero() {
+                let delegations = query_delegation_by_addr(
+                    deps,
+                    pool_addr.to_string(),
+                    pool_info.sdk_greater_or_equal_v047,
+                )?;
+                if delegations.last_submitted_local_height <= last_step_height {
+                    return Ok(Some(EraProcessWait::IcqUpdate {
+                        query_kind: QueryKind::Delegations,
+                        last_step_height,
+                        last_submitted_local_height: delegations.last_submitted_local_height,
+                    }));
+                }
+            }
+        }
+        EraProcessStep::EraActive => {
+            if !pool_info.share_tokens.is_empty() {
+                return Ok(Some(EraProcessWait::PendingShareTokens));
+            }
+            let delegations = query_delegation_by_addr(
+                deps,
+                pool_addr.to_string(),
+                pool_info.sdk_greater_or_equal_v047,
+            )?;
+            if delegations.last_submitted_local_height <= last_step_height {
+                return Ok(Some(EraProcessWait::IcqUpdate {
+                    query_kind: QueryKind::Delegations,
+                    last_step_height,
+                    last_submitted_local_height: delegations.last_submitted_local_height,
+                }));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn era_process_response(
+    rsp: Response<NeutronMsg>,
+    pool_addr: String,
+    pool_info: &PoolInfo,
+    executed_step: Option<EraProcessStep>,
+    wait: Option<EraProcessWait>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let (next_step, _) = era_step_of_status(&pool_info.status)?;
+
+    let data = EraProcessResponse {
+        pool_addr: pool_addr.clone(),
+        era: pool_info.era,
+        status: pool_info.status.clone(),
+        executed_step,
+        next_step,
+        wait,
+    };
+
+    Ok(rsp
+        .set_data(to_json_binary(&data)?)
+        .add_attribute("action", "era_process")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("era", pool_info.era.to_string()))
+}
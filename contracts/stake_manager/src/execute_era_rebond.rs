@@ -1,11 +1,18 @@
 use crate::error_conversion::ContractError;
+use crate::execute_roles::check_era_operator;
+use crate::slashing::add_expected_delegations;
 use crate::state::EraStatus::{RebondEnded, RebondStarted, WithdrawEnded};
-use crate::state::{INFO_OF_ICA_ID, POOLS};
+use crate::state::{FeeMode, INFO_OF_ICA_ID, POOLS, STACK};
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::{
-    helper::{check_ibc_fee, gen_delegation_txs, DEFAULT_TIMEOUT_SECONDS},
+    helper::{
+        check_ibc_fee, check_ica_channel_open, gen_delegation_txs, gen_msg_send, gen_msg_transfer,
+        tx_timeout_seconds, CAL_BASE,
+    },
     state::{SudoPayload, TxType},
     tx_callback::msg_with_sudo_callback,
 };
+use crate::{query::query_delegation_by_addr, validator_selection::allocate_delegate_amount};
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
@@ -15,11 +22,12 @@ use std::ops::{Div, Mul, Sub};
 
 pub fn execute_era_rebond(
     mut deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     pool_addr: String,
-    select_vals: Vec<String>,
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    check_era_operator(deps.storage, &pool_info, pool_addr.clone(), &info.sender)?;
 
     // check era state
     if pool_info.status != WithdrawEnded {
@@ -28,8 +36,9 @@ pub fn execute_era_rebond(
     pool_info.status = RebondStarted;
 
     let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    check_ica_channel_open(&pool_ica_info)?;
 
-    let restake_amount = pool_info.era_snapshot.restake_amount;
+    let mut restake_amount = pool_info.era_snapshot.restake_amount;
 
     // leave gas
     if restake_amount.is_zero() {
@@ -38,43 +47,81 @@ pub fn execute_era_rebond(
         return Ok(Response::default());
     }
 
-    let validator_count = select_vals.len() as u128;
-    let mut msgs = vec![];
-    if validator_count == 0 {
-        return Err(ContractError::ValidatorsEmpty {}.into());
+    let delegations = query_delegation_by_addr(
+        deps.as_ref(),
+        pool_addr.clone(),
+        pool_info.sdk_greater_or_equal_v047,
+    )?;
+    if delegations.last_submitted_local_height <= pool_info.era_snapshot.last_step_height {
+        return Err(ContractError::DelegationSubmissionHeight {}.into());
     }
 
-    let amount_per_validator = restake_amount.div(Uint128::from(validator_count));
-    let remainder = restake_amount.sub(amount_per_validator.mul(Uint128::new(validator_count)));
-
-    for (index, validator_addr) in select_vals.iter().enumerate() {
-        if !pool_info.validator_addrs.contains(validator_addr) {
-            return Err(ContractError::ValidatorNotSupport {}.into());
-        }
+    let mut msgs = vec![];
 
-        let mut amount_for_this_validator = amount_per_validator;
-        // Add the remainder to the first validator
-        if index == 0 {
-            amount_for_this_validator += remainder;
+    // a native fee mode pays the platform share of the fee out of the restaked rewards
+    pool_info.era_snapshot.native_platform_fee = Uint128::zero();
+    if pool_info.fee_mode != FeeMode::LsdToken {
+        let stack_info = STACK.load(deps.storage)?;
+        let native_platform_fee = restake_amount
+            .mul(pool_info.platform_fee_commission)
+            .div(CAL_BASE)
+            .mul(CAL_BASE.sub(stack_info.stack_fee_commission))
+            .div(CAL_BASE);
+        if !native_platform_fee.is_zero() && native_platform_fee < restake_amount {
+            let fee_msg = match pool_info.fee_mode.clone() {
+                FeeMode::NativeHostChain { receiver } => Some(gen_msg_send(
+                    pool_addr.clone(),
+                    receiver,
+                    pool_info.remote_denom.clone(),
+                    native_platform_fee.to_string(),
+                )?),
+                // the transfer module refunds the pool ica if the transfer to neutron fails,
+                // see total_native_platform_fee
+                FeeMode::NativeNeutron => Some(gen_msg_transfer(
+                    pool_info.host_transfer_channel_id.clone(),
+                    pool_addr.clone(),
+                    pool_info.platform_fee_receiver.to_string(),
+                    pool_info.remote_denom.clone(),
+                    native_platform_fee.to_string(),
+                    env.block.time.nanos()
+                        + tx_timeout_seconds(&pool_info, &TxType::EraRebond) * 1_000_000_000,
+                )?),
+                FeeMode::LsdToken => None,
+            };
+            if let Some(fee_msg) = fee_msg {
+                msgs.push(fee_msg);
+                restake_amount = restake_amount.sub(native_platform_fee);
+                pool_info.era_snapshot.native_platform_fee = native_platform_fee;
+            }
         }
+    }
 
-        let any_msg = gen_delegation_txs(
+    let delegate_infos =
+        allocate_delegate_amount(&mut pool_info, &delegations.delegations, restake_amount)?;
+    add_expected_delegations(
+        deps.storage,
+        pool_addr.clone(),
+        &delegate_infos
+            .iter()
+            .map(|info| (info.validator.clone(), info.delegate_amount))
+            .collect::<Vec<_>>(),
+    )?;
+    for info in delegate_infos {
+        msgs.push(gen_delegation_txs(
             pool_addr.clone(),
-            validator_addr.clone(),
+            info.validator,
             pool_info.remote_denom.clone(),
-            amount_for_this_validator,
-        );
-
-        msgs.push(any_msg);
+            info.delegate_amount,
+        ));
     }
 
     let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
     let cosmos_msg = NeutronMsg::submit_tx(
         pool_ica_info.ctrl_connection_id.clone(),
-        pool_info.ica_id,
+        pool_info.ica_id.clone(),
         msgs,
         "".to_string(),
-        DEFAULT_TIMEOUT_SECONDS,
+        tx_timeout_seconds(&pool_info, &TxType::EraRebond),
         ibc_fee,
     );
 
@@ -83,13 +130,15 @@ pub fn execute_era_rebond(
         cosmos_msg,
         SudoPayload {
             port_id: pool_ica_info.ctrl_port_id,
-            // the acknowledgement later
-            message: "".to_string(),
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::Empty {},
             pool_addr: pool_addr.clone(),
             tx_type: TxType::EraRebond,
         },
     )?;
 
+    POOLS.save(deps.storage, pool_addr, &pool_info)?;
+
     Ok(Response::default().add_submessage(submsg))
 }
 
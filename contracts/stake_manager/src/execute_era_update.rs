@@ -1,7 +1,9 @@
+use crate::execute_roles::check_era_operator;
 use crate::helper::{
-    check_ibc_fee, get_update_pool_icq_msgs, DEFAULT_FAST_PERIOD, DEFAULT_TIMEOUT_SECONDS,
+    check_ibc_fee, get_update_pool_icq_msgs, tx_timeout_seconds, DEFAULT_FAST_PERIOD,
 };
 use crate::state::EraSnapshot;
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::state::{INFO_OF_ICA_ID, POOLS};
 use crate::{
     error_conversion::ContractError,
@@ -29,6 +31,7 @@ pub fn execute_era_update(
     pool_addr: String,
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    check_era_operator(deps.storage, &pool_info, pool_addr.clone(), &info.sender)?;
     if pool_info.paused {
         return Err(ContractError::PoolIsPaused {}.into());
     }
@@ -49,6 +52,14 @@ pub fn execute_era_update(
         return Err(ContractError::AlreadyLatestEra {}.into());
     }
 
+    // replenish the liquidity buffer from the net bond instead of bridging all of it
+    let buffer_top_up = pool_info
+        .liquidity_buffer_target
+        .saturating_sub(pool_info.liquidity_buffer)
+        .min(pool_info.bond.saturating_sub(pool_info.unbond));
+    pool_info.bond = pool_info.bond.sub(buffer_top_up);
+    pool_info.liquidity_buffer = pool_info.liquidity_buffer.add(buffer_top_up);
+
     pool_info.status = EraUpdateStarted;
     pool_info.era = pool_info.era.add(1);
     pool_info.era_snapshot = EraSnapshot {
@@ -58,6 +69,8 @@ pub fn execute_era_update(
         active: pool_info.active,
         last_step_height: env.block.height,
         restake_amount: Uint128::zero(),
+        liquidity_buffer: pool_info.liquidity_buffer,
+        native_platform_fee: Uint128::zero(),
     };
     let rsp = Response::default().add_messages(get_update_pool_icq_msgs(
         deps.branch(),
@@ -89,7 +102,8 @@ pub fn execute_era_update(
             revision_number: None,
             revision_height: None,
         },
-        timeout_timestamp: env.block.time.nanos() + DEFAULT_TIMEOUT_SECONDS * 1_000_000_000,
+        timeout_timestamp: env.block.time.nanos()
+            + tx_timeout_seconds(&pool_info, &TxType::EraUpdate) * 1_000_000_000,
         memo: "".to_string(),
         fee: ibc_fee,
     };
@@ -102,7 +116,8 @@ pub fn execute_era_update(
         SudoPayload {
             port_id: pool_ica_info.ctrl_port_id,
             pool_addr: pool_addr.clone(),
-            message: "".to_string(),
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::Empty {},
             tx_type: TxType::EraUpdate,
         },
     )?;
@@ -139,6 +154,8 @@ pub fn sudo_era_update_failed_callback(
         active: Uint128::zero(),
         restake_amount: Uint128::zero(),
         last_step_height: 0,
+        liquidity_buffer: Uint128::zero(),
+        native_platform_fee: Uint128::zero(),
     };
 
     POOLS.save(deps.storage, payload.pool_addr.clone(), &pool_info)?;
@@ -1,10 +1,14 @@
 use crate::helper::{
     self, deal_pool, min_ntrn_ibc_fee, query_icq_register_fee, set_withdraw_sub_msg,
-    total_icq_register_fee, CAL_BASE, DEFAULT_ERA_SECONDS, MIN_ERA_SECONDS, VALIDATER_LEN_LIMIT,
+    total_icq_register_fee, CAL_BASE, DEFAULT_ERA_SECONDS, DEFAULT_MAX_ENTRIES, MIN_ERA_SECONDS,
+    VALIDATER_LEN_LIMIT,
 };
 use crate::msg::InitPoolParams;
-use crate::state::ValidatorUpdateStatus;
 use crate::state::POOLS;
+use crate::state::{
+    ExtraRewardRoute, FeeMode, SlashPolicy, UnbondCommissionSchedule, ValidatorSelectStrategy,
+    ValidatorUpdateStatus,
+};
 use crate::state::{INFO_OF_ICA_ID, STACK};
 use crate::{error_conversion::ContractError, state::EraStatus};
 use cosmwasm_std::{Addr, Uint128};
@@ -114,6 +118,29 @@ pub fn execute_init_pool(
     pool_info.lsm_pending_limit = 100;
     pool_info.rate_change_limit = Uint128::zero();
     pool_info.validator_update_status = ValidatorUpdateStatus::End;
+    pool_info.validator_select_strategy = ValidatorSelectStrategy::Average;
+    pool_info.round_robin_index = 0;
+    pool_info.validator_weights = vec![];
+    pool_info.rebalance_tolerance = 500;
+    pool_info.max_entries = DEFAULT_MAX_ENTRIES;
+    pool_info.slash_policy = SlashPolicy::Socialize;
+    pool_info.slash_unstake_haircut = false;
+    pool_info.inactive_validators = vec![];
+    pool_info.auto_redelegate_inactive = false;
+    pool_info.liquidity_buffer = Uint128::zero();
+    pool_info.liquidity_buffer_target = Uint128::zero();
+    pool_info.liquidity_buffer_ratio = Uint128::zero();
+    pool_info.instant_unstake_fee_min = Uint128::zero();
+    pool_info.instant_unstake_fee_max = Uint128::zero();
+    pool_info.unstake_nft = None;
+    pool_info.withdraw_for_tip = Uint128::zero();
+    pool_info.host_transfer_channel_id = "".to_string();
+    pool_info.tx_timeouts = vec![];
+    pool_info.extra_reward_denoms = vec![];
+    pool_info.extra_reward_route = ExtraRewardRoute::Keep;
+    pool_info.fee_mode = FeeMode::LsdToken;
+    pool_info.unbond_commission_schedule = UnbondCommissionSchedule::Flat;
+    pool_info.total_native_platform_fee = Uint128::zero();
 
     let code_id = match param.lsd_code_id {
         Some(lsd_code_id) => lsd_code_id,
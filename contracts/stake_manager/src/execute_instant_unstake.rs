@@ -0,0 +1,93 @@
+use std::ops::{Add, Div, Mul, Sub};
+use std::vec;
+
+use cosmwasm_std::{
+    coins, to_json_binary, BankMsg, CosmosMsg, DepsMut, MessageInfo, Response, Uint128, WasmMsg,
+};
+use neutron_sdk::{
+    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    NeutronResult,
+};
+
+use crate::state::{PoolInfo, POOLS};
+use crate::unbond_commission::consume_stake_record;
+use crate::{error_conversion::ContractError, helper::CAL_BASE};
+
+// Before this step, need the user to authorize burn from
+pub fn execute_instant_unstake(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    lsd_token_amount: Uint128,
+    pool_addr: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    if lsd_token_amount.is_zero() {
+        return Err(ContractError::EncodeErrLsdTokenAmountZero {}.into());
+    }
+
+    let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    if pool_info.paused {
+        return Err(ContractError::PoolIsPaused {}.into());
+    }
+
+    let token_amount = lsd_token_amount.mul(pool_info.rate).div(CAL_BASE);
+    let fee = instant_unstake_fee(&pool_info, token_amount)?;
+    let receive_amount = token_amount.sub(fee);
+    if receive_amount.is_zero() {
+        return Err(ContractError::EncodeErrZeroWithdrawAmount {}.into());
+    }
+
+    // the fee stays in the buffer and active, which raises the rate for holders
+    pool_info.liquidity_buffer = pool_info.liquidity_buffer.sub(receive_amount);
+    pool_info.active = pool_info.active.sub(receive_amount);
+    pool_info.total_lsd_token_amount = pool_info.total_lsd_token_amount.sub(lsd_token_amount);
+    consume_stake_record(
+        deps.storage,
+        info.sender.clone(),
+        pool_addr.clone(),
+        lsd_token_amount,
+    )?;
+
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: pool_info.lsd_token.to_string(),
+        msg: to_json_binary(
+            &(lsd_token::msg::ExecuteMsg::BurnFrom {
+                owner: info.sender.to_string(),
+                amount: lsd_token_amount,
+            }),
+        )?,
+        funds: vec![],
+    };
+    let send_msg = BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: coins(receive_amount.u128(), pool_info.ibc_denom.clone()),
+    };
+
+    POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(burn_msg))
+        .add_message(send_msg)
+        .add_attribute("action", "instant_unstake")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("from", info.sender.to_string())
+        .add_attribute("lsd_token_amount", lsd_token_amount)
+        .add_attribute("token_amount", token_amount)
+        .add_attribute("fee", fee)
+        .add_attribute("receive_amount", receive_amount))
+}
+
+// fee rate grows linearly from min to max with the share of the buffer the unstake takes
+pub fn instant_unstake_fee(pool_info: &PoolInfo, token_amount: Uint128) -> NeutronResult<Uint128> {
+    if pool_info.liquidity_buffer.is_zero() || token_amount > pool_info.liquidity_buffer {
+        return Err(ContractError::LiquidityBufferNotEnough {}.into());
+    }
+
+    let fee_rate = pool_info.instant_unstake_fee_min.add(
+        pool_info
+            .instant_unstake_fee_max
+            .sub(pool_info.instant_unstake_fee_min)
+            .multiply_ratio(token_amount, pool_info.liquidity_buffer),
+    );
+
+    Ok(token_amount.mul(fee_rate).div(CAL_BASE))
+}
@@ -5,10 +5,14 @@ use crate::helper::query_icq_register_fee;
 use crate::helper::set_withdraw_sub_msg;
 use crate::helper::total_icq_register_fee;
 use crate::helper::CAL_BASE;
+use crate::helper::DEFAULT_MAX_ENTRIES;
 use crate::helper::VALIDATER_LEN_LIMIT;
 use crate::msg::MigratePoolParams;
-use crate::state::ValidatorUpdateStatus;
 use crate::state::POOLS;
+use crate::state::{
+    ExtraRewardRoute, FeeMode, SlashPolicy, UnbondCommissionSchedule, ValidatorSelectStrategy,
+    ValidatorUpdateStatus,
+};
 use crate::state::{INFO_OF_ICA_ID, STACK};
 use crate::{error_conversion::ContractError, state::EraStatus};
 use cosmwasm_std::{Addr, Uint128};
@@ -106,6 +110,29 @@ pub fn execute_migrate_pool(
     pool_info.lsm_pending_limit = 50;
     pool_info.rate_change_limit = Uint128::zero();
     pool_info.validator_update_status = ValidatorUpdateStatus::End;
+    pool_info.validator_select_strategy = ValidatorSelectStrategy::Average;
+    pool_info.round_robin_index = 0;
+    pool_info.validator_weights = vec![];
+    pool_info.rebalance_tolerance = 500;
+    pool_info.max_entries = DEFAULT_MAX_ENTRIES;
+    pool_info.slash_policy = SlashPolicy::Socialize;
+    pool_info.slash_unstake_haircut = false;
+    pool_info.inactive_validators = vec![];
+    pool_info.auto_redelegate_inactive = false;
+    pool_info.liquidity_buffer = Uint128::zero();
+    pool_info.liquidity_buffer_target = Uint128::zero();
+    pool_info.liquidity_buffer_ratio = Uint128::zero();
+    pool_info.instant_unstake_fee_min = Uint128::zero();
+    pool_info.instant_unstake_fee_max = Uint128::zero();
+    pool_info.unstake_nft = None;
+    pool_info.withdraw_for_tip = Uint128::zero();
+    pool_info.host_transfer_channel_id = "".to_string();
+    pool_info.tx_timeouts = vec![];
+    pool_info.extra_reward_denoms = vec![];
+    pool_info.extra_reward_route = ExtraRewardRoute::Keep;
+    pool_info.fee_mode = FeeMode::LsdToken;
+    pool_info.unbond_commission_schedule = UnbondCommissionSchedule::Flat;
+    pool_info.total_native_platform_fee = Uint128::zero();
 
     // check rate
     let cal_rate = if pool_info.total_lsd_token_amount.is_zero() {
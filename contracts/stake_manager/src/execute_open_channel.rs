@@ -54,3 +54,48 @@ pub fn execute_open_channel(
 
     Ok(Response::default().add_messages(msgs))
 }
+
+// anyone may re-register an ica whose channel was closed by a timeout, the ica id and address are kept
+pub fn execute_reopen_channel(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    pool_addr: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let pool_info = POOLS.load(deps.as_ref().storage, pool_addr.clone())?;
+    let register_fee = if !info.funds.is_empty() {
+        Some(info.funds)
+    } else {
+        None
+    };
+
+    let mut msgs = vec![];
+    let mut reopened = vec![];
+
+    let (pool_ica_info, withdraw_ica_info, _) =
+        INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    if pool_ica_info.closed {
+        msgs.push(NeutronMsg::register_interchain_account(
+            pool_ica_info.ctrl_connection_id.clone(),
+            pool_info.ica_id.clone(),
+            register_fee.clone(),
+        ));
+        reopened.push(pool_ica_info.ctrl_channel_id);
+    }
+    if withdraw_ica_info.closed {
+        msgs.push(NeutronMsg::register_interchain_account(
+            withdraw_ica_info.ctrl_connection_id.clone(),
+            get_withdraw_ica_id(pool_info.ica_id),
+            register_fee,
+        ));
+        reopened.push(withdraw_ica_info.ctrl_channel_id);
+    }
+    if msgs.is_empty() {
+        return Err(ContractError::IcaChannelNotClosed {}.into());
+    }
+
+    Ok(Response::default()
+        .add_messages(msgs)
+        .add_attribute("action", "reopen_channel")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("closed_channel_ids", reopened.join(",")))
+}
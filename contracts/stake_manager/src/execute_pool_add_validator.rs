@@ -1,3 +1,5 @@
+use crate::execute_roles::check_pool_role;
+use crate::state::Role;
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 
 use neutron_sdk::{
@@ -18,9 +20,13 @@ pub fn execute_add_pool_validators(
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
 
-    if info.sender != pool_info.admin {
-        return Err(ContractError::Unauthorized {}.into());
-    }
+    check_pool_role(
+        deps.storage,
+        &pool_info,
+        pool_addr.clone(),
+        &info.sender,
+        &Role::ValidatorManager,
+    )?;
     if pool_info.status != EraStatus::ActiveEnded {
         return Err(ContractError::EraProcessNotEnd {}.into());
     }
@@ -1,13 +1,13 @@
-use crate::helper::{check_ibc_fee, DEFAULT_TIMEOUT_SECONDS};
+use crate::helper::{check_ibc_fee, check_ica_channel_open, tx_timeout_seconds};
 use crate::state::EraStatus::ActiveEnded;
-use crate::state::{INFO_OF_ICA_ID, POOLS};
+use crate::state::{TxType, INFO_OF_ICA_ID, POOLS};
+use crate::validator_selection::{check_plan_covers, split_by_weight};
 use crate::{error_conversion::ContractError, helper::gen_delegation_txs};
 use cosmwasm_std::{DepsMut, MessageInfo, Response, SubMsg, Uint128};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
 };
-use std::ops::{Div, Mul, Sub};
 use std::vec;
 
 pub fn execute_pool_delegate(
@@ -26,34 +26,27 @@ pub fn execute_pool_delegate(
         return Err(ContractError::Unauthorized {}.into());
     }
 
-    let validator_count = pool_info.validator_addrs.len() as u128;
-    if validator_count == 0 {
+    if pool_info.validator_addrs.is_empty() {
         return Err(ContractError::ValidatorsEmpty {}.into());
     }
 
-    let mut msgs = vec![];
-    let amount_per_validator = stake_amount.div(Uint128::from(validator_count));
-    let remainder = stake_amount.sub(amount_per_validator.mul(Uint128::new(validator_count)));
-
-    for (index, validator_addr) in pool_info.validator_addrs.iter().enumerate() {
-        let mut amount_for_this_validator = amount_per_validator;
-
-        // Add the remainder to the first validator
-        if index == 0 {
-            amount_for_this_validator += remainder;
-        }
+    let plan = split_by_weight(&pool_info, stake_amount);
+    check_plan_covers(&plan, stake_amount)?;
 
+    let mut msgs = vec![];
+    for delegate_info in plan {
         let any_msg = gen_delegation_txs(
             pool_addr.clone(),
-            validator_addr.clone(),
+            delegate_info.validator,
             pool_info.remote_denom.clone(),
-            amount_for_this_validator,
+            delegate_info.delegate_amount,
         );
 
         msgs.push(any_msg);
     }
 
     let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    check_ica_channel_open(&pool_ica_info)?;
 
     let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
     let cosmos_msg = NeutronMsg::submit_tx(
@@ -61,7 +54,8 @@ pub fn execute_pool_delegate(
         pool_info.ica_id.clone(),
         msgs,
         "".to_string(),
-        DEFAULT_TIMEOUT_SECONDS,
+        // delegates like era bond
+        tx_timeout_seconds(&pool_info, &TxType::EraBond),
         ibc_fee,
     );
 
@@ -0,0 +1,149 @@
+use crate::error_conversion::ContractError;
+use crate::helper::{
+    check_ibc_fee, check_ica_channel_open, gen_redelegate_txs, tx_timeout_seconds,
+};
+use crate::query::query_delegation_by_addr;
+use crate::redelegation::{load_redelegation_limits, settle_redelegating_pairs};
+use crate::state::{EraStatus, SudoPayload, TxType, ValidatorUpdateStatus, INFO_OF_ICA_ID, POOLS};
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
+use crate::tx_callback::msg_with_sudo_callback;
+use crate::validator_selection::rebalance_plan;
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use neutron_sdk::{
+    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    NeutronResult,
+};
+
+pub fn execute_pool_rebalance(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    pool_addr: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let mut pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+
+    if info.sender != pool_info.admin {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+    if pool_info.status != EraStatus::ActiveEnded {
+        return Err(ContractError::EraProcessNotEnd {}.into());
+    }
+    if pool_info.validator_update_status != ValidatorUpdateStatus::End {
+        return Err(ContractError::StatusNotAllow {}.into());
+    }
+
+    let delegations = query_delegation_by_addr(
+        deps.as_ref(),
+        pool_addr.clone(),
+        pool_info.sdk_greater_or_equal_v047,
+    )?;
+    if delegations.last_submitted_local_height <= pool_info.era_snapshot.last_step_height {
+        return Err(ContractError::DelegationSubmissionHeight {}.into());
+    }
+
+    let limits = load_redelegation_limits(
+        deps.storage,
+        &pool_info,
+        pool_addr.clone(),
+        env.block.time.seconds(),
+    )?;
+    let plan = rebalance_plan(&pool_info, &delegations.delegations, &limits);
+    if plan.is_empty() {
+        return Err(ContractError::DriftWithinTolerance {}.into());
+    }
+
+    let mut rsp = Response::new()
+        .add_attribute("action", "pool_rebalance")
+        .add_attribute("pool", pool_addr.clone());
+    let mut msgs = vec![];
+    let mut pairs = vec![];
+    for redelegate_info in plan {
+        rsp = rsp.add_attribute(
+            "redelegate",
+            format!(
+                "{}:{}:{}",
+                redelegate_info.src_validator,
+                redelegate_info.dst_validator,
+                redelegate_info.amount
+            ),
+        );
+        pairs.push((
+            redelegate_info.src_validator.clone(),
+            redelegate_info.dst_validator.clone(),
+        ));
+        msgs.push(gen_redelegate_txs(
+            pool_addr.clone(),
+            redelegate_info.src_validator,
+            redelegate_info.dst_validator,
+            pool_info.remote_denom.clone(),
+            redelegate_info.amount,
+        ));
+    }
+
+    let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    check_ica_channel_open(&pool_ica_info)?;
+    let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
+    let submsg_redelegate = msg_with_sudo_callback(
+        deps.branch(),
+        NeutronMsg::submit_tx(
+            pool_ica_info.ctrl_connection_id.clone(),
+            pool_info.ica_id.clone(),
+            msgs,
+            "".to_string(),
+            tx_timeout_seconds(&pool_info, &TxType::PoolRebalance),
+            ibc_fee,
+        ),
+        SudoPayload {
+            port_id: pool_ica_info.ctrl_port_id,
+            pool_addr: pool_addr.clone(),
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::Redelegate {
+                redelegating_pairs: pairs,
+            },
+            tx_type: TxType::PoolRebalance,
+        },
+    )?;
+
+    pool_info.validator_update_status = ValidatorUpdateStatus::Start;
+    POOLS.save(deps.storage, pool_addr, &pool_info)?;
+
+    Ok(rsp.add_submessage(submsg_redelegate))
+}
+
+pub fn sudo_pool_rebalance_callback(
+    deps: DepsMut,
+    env: Env,
+    payload: SudoPayload,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let SudoData::Redelegate { redelegating_pairs } = payload.data else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
+    let mut pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
+
+    settle_redelegating_pairs(
+        deps.storage,
+        &pool_info,
+        payload.pool_addr.clone(),
+        redelegating_pairs,
+        env.block.time.seconds(),
+    )?;
+
+    pool_info.validator_update_status = ValidatorUpdateStatus::WaitQueryUpdate;
+
+    POOLS.save(deps.storage, payload.pool_addr, &pool_info)?;
+
+    Ok(Response::new())
+}
+
+pub fn sudo_pool_rebalance_failed_callback(
+    deps: DepsMut,
+    payload: SudoPayload,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let mut pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
+
+    pool_info.validator_update_status = ValidatorUpdateStatus::End;
+
+    POOLS.save(deps.storage, payload.pool_addr, &pool_info)?;
+
+    Ok(Response::new())
+}
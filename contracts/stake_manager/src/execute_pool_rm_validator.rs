@@ -1,11 +1,18 @@
 use crate::error_conversion::ContractError;
-use crate::helper::check_ibc_fee;
+use crate::execute_roles::check_pool_role;
 use crate::helper::gen_redelegate_txs;
-use crate::helper::DEFAULT_TIMEOUT_SECONDS;
+use crate::helper::tx_timeout_seconds;
+use crate::helper::{check_ibc_fee, check_ica_channel_open};
 use crate::query::query_delegation_by_addr;
+use crate::redelegation::{
+    check_redelegation, load_redelegation_limits, settle_redelegating_pairs,
+};
+use crate::state::RedelegationLimit;
+use crate::state::Role;
 use crate::state::{EraStatus, SudoPayload, TxType, ValidatorUpdateStatus, INFO_OF_ICA_ID, POOLS};
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::tx_callback::msg_with_sudo_callback;
-use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
@@ -14,15 +21,20 @@ use std::vec;
 
 pub fn execute_rm_pool_validator(
     mut deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     pool_addr: String,
     validator_addr: String,
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.as_ref().storage, pool_addr.clone())?;
 
-    if info.sender != pool_info.admin {
-        return Err(ContractError::Unauthorized {}.into());
-    }
+    check_pool_role(
+        deps.storage,
+        &pool_info,
+        pool_addr.clone(),
+        &info.sender,
+        &Role::ValidatorManager,
+    )?;
     if pool_info.status != EraStatus::ActiveEnded {
         return Err(ContractError::EraProcessNotEnd {}.into());
     }
@@ -58,10 +70,46 @@ pub fn execute_rm_pool_validator(
         if to_be_redelegate_delegation.amount.amount.is_zero() {
             pool_info.validator_update_status = ValidatorUpdateStatus::WaitQueryUpdate;
             pool_info.validator_addrs = left_validators;
+            pool_info
+                .validator_weights
+                .retain(|w| w.validator != validator_addr);
         } else {
+            let limits = load_redelegation_limits(
+                deps.storage,
+                &pool_info,
+                pool_addr.clone(),
+                env.block.time.seconds(),
+            )?;
+            // redelegate to the first left validator whose pair still has free entries
+            let mut dst_validator = None;
+            for left_validator in left_validators.iter() {
+                match check_redelegation(&limits, &validator_addr, left_validator) {
+                    Some(RedelegationLimit::TransitiveRedelegation { until, .. }) => {
+                        return Err(ContractError::TransitiveRedelegation(
+                            validator_addr.clone(),
+                            until,
+                        )
+                        .into());
+                    }
+                    Some(RedelegationLimit::MaxEntries { .. }) => continue,
+                    None => {
+                        dst_validator = Some(left_validator.clone());
+                        break;
+                    }
+                }
+            }
+            let Some(dst_validator) = dst_validator else {
+                return Err(ContractError::RedelegationEntriesFull(
+                    validator_addr.clone(),
+                    left_validators.join(","),
+                )
+                .into());
+            };
+
             let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
             let (pool_ica_info, _, _) =
                 INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+            check_ica_channel_open(&pool_ica_info)?;
 
             let cosmos_msg = NeutronMsg::submit_tx(
                 pool_ica_info.ctrl_connection_id.clone(),
@@ -69,12 +117,12 @@ pub fn execute_rm_pool_validator(
                 vec![gen_redelegate_txs(
                     pool_addr.clone(),
                     to_be_redelegate_delegation.validator.clone(),
-                    left_validators.get(0).unwrap().to_string(), // redelegate to first
+                    dst_validator.clone(),
                     pool_info.remote_denom.clone(),
                     to_be_redelegate_delegation.amount.amount,
                 )],
                 "".to_string(),
-                DEFAULT_TIMEOUT_SECONDS,
+                tx_timeout_seconds(&pool_info, &TxType::RmValidator),
                 ibc_fee,
             );
 
@@ -84,7 +132,11 @@ pub fn execute_rm_pool_validator(
                 SudoPayload {
                     port_id: pool_ica_info.ctrl_port_id,
                     pool_addr: pool_ica_info.ica_addr.clone(),
-                    message: validator_addr,
+                    version: SUDO_PAYLOAD_VERSION,
+                    data: SudoData::RmValidator {
+                        validator: validator_addr.clone(),
+                        redelegating_pairs: vec![(validator_addr.clone(), dst_validator)],
+                    },
                     tx_type: TxType::RmValidator,
                 },
             )?;
@@ -95,6 +147,9 @@ pub fn execute_rm_pool_validator(
     } else {
         pool_info.validator_update_status = ValidatorUpdateStatus::WaitQueryUpdate;
         pool_info.validator_addrs = left_validators;
+        pool_info
+            .validator_weights
+            .retain(|w| w.validator != validator_addr);
     }
 
     POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
@@ -104,13 +159,30 @@ pub fn execute_rm_pool_validator(
 
 pub fn sudo_rm_validator_callback(
     deps: DepsMut,
+    env: Env,
     payload: SudoPayload,
 ) -> NeutronResult<Response<NeutronMsg>> {
+    let SudoData::RmValidator {
+        validator,
+        redelegating_pairs,
+    } = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
     let mut pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
 
+    settle_redelegating_pairs(
+        deps.storage,
+        &pool_info,
+        payload.pool_addr.clone(),
+        redelegating_pairs,
+        env.block.time.seconds(),
+    )?;
+
+    pool_info.validator_addrs.retain(|v| *v != validator);
     pool_info
-        .validator_addrs
-        .retain(|v| v.to_string() != payload.message);
+        .validator_weights
+        .retain(|w| w.validator != validator);
     pool_info.validator_update_status = ValidatorUpdateStatus::WaitQueryUpdate;
 
     POOLS.save(deps.storage, payload.pool_addr.clone(), &pool_info)?;
@@ -1,5 +1,11 @@
-use crate::helper::check_ibc_fee;
-use crate::state::INFO_OF_ICA_ID;
+use crate::execute_roles::check_pool_role;
+use crate::helper::{check_ibc_fee, check_ica_channel_open};
+use crate::redelegation::{
+    check_redelegation, load_redelegation_limits, settle_redelegating_pairs,
+};
+use crate::state::Role;
+use crate::state::{RedelegationLimit, INFO_OF_ICA_ID};
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::state::{ValidatorUpdateStatus, POOLS};
 use crate::{error_conversion::ContractError, state::EraStatus};
 use crate::{
@@ -7,8 +13,8 @@ use crate::{
     state::{SudoPayload, TxType},
     tx_callback::msg_with_sudo_callback,
 };
-use crate::{helper::DEFAULT_TIMEOUT_SECONDS, query::query_delegation_by_addr};
-use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use crate::{helper::tx_timeout_seconds, query::query_delegation_by_addr};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
@@ -17,6 +23,7 @@ use std::vec;
 
 pub fn execute_pool_update_validator(
     mut deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     pool_addr: String,
     old_validator: String,
@@ -25,9 +32,13 @@ pub fn execute_pool_update_validator(
     let mut pool_info: crate::state::PoolInfo =
         POOLS.load(deps.as_ref().storage, pool_addr.clone())?;
 
-    if info.sender != pool_info.admin {
-        return Err(ContractError::Unauthorized {}.into());
-    }
+    check_pool_role(
+        deps.storage,
+        &pool_info,
+        pool_addr.clone(),
+        &info.sender,
+        &Role::ValidatorManager,
+    )?;
     if pool_info.status != EraStatus::ActiveEnded {
         return Err(ContractError::EraProcessNotEnd {}.into());
     }
@@ -75,11 +86,29 @@ pub fn execute_pool_update_validator(
         msgs.push(any_msg);
     }
     let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    check_ica_channel_open(&pool_ica_info)?;
 
     // let remove_msg_old_query = NeutronMsg::remove_interchain_query(registere_query_id);
     let mut resp = Response::default(); // .add_message(remove_msg_old_query)
 
     if !msgs.is_empty() {
+        let limits = load_redelegation_limits(
+            deps.storage,
+            &pool_info,
+            pool_addr.clone(),
+            env.block.time.seconds(),
+        )?;
+        match check_redelegation(&limits, &old_validator, &new_validator) {
+            Some(RedelegationLimit::TransitiveRedelegation { until, .. }) => {
+                return Err(ContractError::TransitiveRedelegation(old_validator, until).into());
+            }
+            Some(RedelegationLimit::MaxEntries { .. }) => {
+                return Err(
+                    ContractError::RedelegationEntriesFull(old_validator, new_validator).into(),
+                );
+            }
+            None => {}
+        }
         let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
 
         let submsg_redelegate = msg_with_sudo_callback(
@@ -89,18 +118,17 @@ pub fn execute_pool_update_validator(
                 pool_info.ica_id.clone(),
                 msgs,
                 "".to_string(),
-                DEFAULT_TIMEOUT_SECONDS,
+                tx_timeout_seconds(&pool_info, &TxType::UpdateValidator),
                 ibc_fee,
             ),
             SudoPayload {
                 port_id: pool_ica_info.ctrl_port_id,
                 pool_addr: pool_ica_info.ica_addr.clone(),
-                message: new_validators
-                    .clone()
-                    .into_iter()
-                    .map(|index| index.to_string())
-                    .collect::<Vec<String>>()
-                    .join("_"),
+                version: SUDO_PAYLOAD_VERSION,
+                data: SudoData::UpdateValidator {
+                    new_validators: new_validators.clone(),
+                    redelegating_pairs: vec![(old_validator.clone(), new_validator.clone())],
+                },
                 tx_type: TxType::UpdateValidator,
             },
         )?;
@@ -113,6 +141,12 @@ pub fn execute_pool_update_validator(
     }
 
     pool_info.validator_addrs = new_validators;
+    // the new validator takes over the target weight of the old one
+    for validator_weight in pool_info.validator_weights.iter_mut() {
+        if validator_weight.validator == old_validator {
+            validator_weight.validator = new_validator.clone();
+        }
+    }
     POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
 
     Ok(resp)
@@ -120,11 +154,25 @@ pub fn execute_pool_update_validator(
 
 pub fn sudo_update_validator_callback(
     deps: DepsMut,
+    env: Env,
     payload: SudoPayload,
 ) -> NeutronResult<Response<NeutronMsg>> {
+    let SudoData::UpdateValidator {
+        new_validators,
+        redelegating_pairs,
+    } = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
     let mut pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
 
-    let new_validators: Vec<String> = payload.message.split('_').map(String::from).collect();
+    settle_redelegating_pairs(
+        deps.storage,
+        &pool_info,
+        payload.pool_addr.clone(),
+        redelegating_pairs,
+        env.block.time.seconds(),
+    )?;
 
     pool_info.validator_addrs = new_validators;
     pool_info.validator_update_status = ValidatorUpdateStatus::WaitQueryUpdate;
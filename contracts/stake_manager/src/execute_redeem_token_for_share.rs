@@ -1,6 +1,9 @@
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::{
     error_conversion::ContractError,
-    helper::{check_ibc_fee, redeem_token_for_share_msg, DEFAULT_TIMEOUT_SECONDS},
+    helper::{
+        check_ibc_fee, check_ica_channel_open, redeem_token_for_share_msg, tx_timeout_seconds,
+    },
     state::POOLS,
 };
 use crate::{
@@ -28,6 +31,7 @@ pub fn execute_redeem_token_for_share(
     }
 
     let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    check_ica_channel_open(&pool_ica_info)?;
 
     let mut denoms = vec![];
     let mut msgs = vec![];
@@ -59,13 +63,13 @@ pub fn execute_redeem_token_for_share(
             pool_info.ica_id.clone(),
             msgs,
             "".to_string(),
-            DEFAULT_TIMEOUT_SECONDS,
+            tx_timeout_seconds(&pool_info, &TxType::RedeemTokenForShare),
             ibc_fee,
         ),
         SudoPayload {
             port_id: pool_ica_info.ctrl_port_id,
-            // the acknowledgement later
-            message: denoms.join(","),
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::RedeemTokenForShare { denoms },
             pool_addr: pool_addr.clone(),
             tx_type: TxType::RedeemTokenForShare,
         },
@@ -82,7 +86,12 @@ pub fn sudo_redeem_token_for_share_callback(
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.as_ref().storage, payload.pool_addr.clone())?;
 
-    let will_removed_denoms: Vec<String> = payload.message.split(",").map(String::from).collect();
+    let SudoData::RedeemTokenForShare {
+        denoms: will_removed_denoms,
+    } = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
 
     pool_info
         .share_tokens
@@ -103,7 +112,12 @@ pub fn sudo_redeem_token_for_share_failed_callback(
 ) -> NeutronResult<Response<NeutronMsg>> {
     let mut pool_info = POOLS.load(deps.as_ref().storage, payload.pool_addr.clone())?;
 
-    let will_removed_denoms: Vec<String> = payload.message.split(",").map(String::from).collect();
+    let SudoData::RedeemTokenForShare {
+        denoms: will_removed_denoms,
+    } = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
 
     pool_info
         .redeemming_share_token_denom
@@ -3,10 +3,15 @@ use std::ops::Div;
 use crate::{
     error_conversion::ContractError,
     helper,
-    state::{EraSnapshot, ValidatorUpdateStatus, STACK},
+    state::{
+        EraSnapshot, ExtraRewardRoute, FeeMode, SlashPolicy, UnbondCommissionSchedule,
+        ValidatorSelectStrategy, ValidatorUpdateStatus, STACK,
+    },
 };
 use crate::{
-    helper::{get_withdraw_ica_id, ICA_WITHDRAW_SUFIX, INTERCHAIN_ACCOUNT_ID_LEN_LIMIT},
+    helper::{
+        get_withdraw_ica_id, parse_ica_port_id, ICA_WITHDRAW_SUFIX, INTERCHAIN_ACCOUNT_ID_LEN_LIMIT,
+    },
     state::{EraStatus, IcaInfo, PoolInfo, INFO_OF_ICA_ID, POOLS},
 };
 use cosmwasm_std::{Addr, Coin, DepsMut, Env, MessageInfo, Response, Uint128};
@@ -92,6 +97,7 @@ pub fn execute_register_pool(
                 host_channel_id: "".to_string(),
                 ctrl_port_id: ctrl_port_id_of_pool,
                 ica_addr: "".to_string(),
+                closed: false,
             },
             IcaInfo {
                 ctrl_connection_id: connection_id.clone(),
@@ -100,6 +106,7 @@ pub fn execute_register_pool(
                 host_channel_id: "".to_string(),
                 ctrl_port_id: ctrl_port_id_of_withdraw,
                 ica_addr: "".to_string(),
+                closed: false,
             },
             info.sender,
         ),
@@ -122,39 +129,25 @@ pub fn sudo_open_ack(
     let parsed_version: Result<OpenAckVersion, _> =
         serde_json_wasm::from_str(counterparty_version.as_str());
     if let Ok(parsed_version) = parsed_version {
-        let port_id_parts: Vec<String> = port_id.split('.').map(String::from).collect();
-        if port_id_parts.len() != 2 {
+        let Some((ica_id, is_withdraw)) = parse_ica_port_id(&port_id) else {
             return Err(ContractError::CounterpartyVersionNotMatch {}.into());
-        }
-
-        let ica_id_raw = port_id_parts.get(1).unwrap();
-        let mut is_pool = true;
-        let ica_id = if ica_id_raw.contains(ICA_WITHDRAW_SUFIX) {
-            is_pool = false;
-            ica_id_raw
-                .strip_suffix(ICA_WITHDRAW_SUFIX)
-                .unwrap()
-                .to_string()
-        } else {
-            ica_id_raw.clone()
         };
 
         let (mut pool_ica_info, mut withdraw_ica_info, admin) =
             INFO_OF_ICA_ID.load(deps.storage, ica_id.clone())?;
 
-        if is_pool {
-            pool_ica_info.ctrl_channel_id = _channel_id;
-            pool_ica_info.ctrl_port_id = port_id;
-            pool_ica_info.host_connection_id = parsed_version.host_connection_id;
-            pool_ica_info.host_channel_id = _counterparty_channel_id;
-            pool_ica_info.ica_addr = parsed_version.address;
+        // a reopened channel keeps the ica address and resumes ica txs
+        let ica_info = if is_withdraw {
+            &mut withdraw_ica_info
         } else {
-            withdraw_ica_info.ctrl_channel_id = _channel_id;
-            withdraw_ica_info.ctrl_port_id = port_id;
-            withdraw_ica_info.host_connection_id = parsed_version.host_connection_id;
-            withdraw_ica_info.host_channel_id = _counterparty_channel_id;
-            withdraw_ica_info.ica_addr = parsed_version.address;
-        }
+            &mut pool_ica_info
+        };
+        ica_info.ctrl_channel_id = _channel_id;
+        ica_info.ctrl_port_id = port_id;
+        ica_info.host_connection_id = parsed_version.host_connection_id;
+        ica_info.host_channel_id = _counterparty_channel_id;
+        ica_info.ica_addr = parsed_version.address;
+        ica_info.closed = false;
 
         if !pool_ica_info.ica_addr.is_empty()
             && !withdraw_ica_info.ica_addr.is_empty()
@@ -195,15 +188,47 @@ pub fn sudo_open_ack(
                     active: Uint128::zero(),
                     restake_amount: Uint128::zero(),
                     last_step_height: 0,
+                    liquidity_buffer: Uint128::zero(),
+                    native_platform_fee: Uint128::zero(),
                 },
                 paused: false,
                 lsm_support: false,
                 lsm_pending_limit: 0,
                 rate_change_limit: Uint128::zero(),
                 sdk_greater_or_equal_v047: false,
+                validator_select_strategy: ValidatorSelectStrategy::Average,
+                round_robin_index: 0,
+                validator_weights: vec![],
+                rebalance_tolerance: 0,
+                max_entries: helper::DEFAULT_MAX_ENTRIES,
+                slash_policy: SlashPolicy::Socialize,
+                slash_unstake_haircut: false,
+                inactive_validators: vec![],
+                auto_redelegate_inactive: false,
+                liquidity_buffer: Uint128::zero(),
+                liquidity_buffer_target: Uint128::zero(),
+                liquidity_buffer_ratio: Uint128::zero(),
+                instant_unstake_fee_min: Uint128::zero(),
+                instant_unstake_fee_max: Uint128::zero(),
+                unstake_nft: None,
+                withdraw_for_tip: Uint128::zero(),
+                host_transfer_channel_id: "".to_string(),
+                tx_timeouts: vec![],
+                extra_reward_denoms: vec![],
+                extra_reward_route: ExtraRewardRoute::Keep,
+                fee_mode: FeeMode::LsdToken,
+                unbond_commission_schedule: UnbondCommissionSchedule::Flat,
+                total_native_platform_fee: Uint128::zero(),
             };
 
             POOLS.save(deps.storage, pool_ica_info.ica_addr.clone(), &pool_info)?;
+
+            // index the registered pool
+            let mut stack = STACK.load(deps.storage)?;
+            if !stack.pools.contains(&pool_ica_info.ica_addr) {
+                stack.pools.push(pool_ica_info.ica_addr.clone());
+                STACK.save(deps.storage, &stack)?;
+            }
         }
 
         INFO_OF_ICA_ID.save(
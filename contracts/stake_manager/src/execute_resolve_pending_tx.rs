@@ -0,0 +1,150 @@
+use crate::error_conversion::ContractError;
+use crate::state::{
+    read_sudo_payload, remove_sudo_payload, PendingTxOutcome, POOLS, SUDO_PAYLOAD_DEADLINE,
+};
+use crate::tx_callback::{sudo_callback, sudo_failed_callback};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use neutron_sdk::{
+    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    NeutronResult,
+};
+
+// settle a payload whose acknowledgement never arrived, as if the ack or timeout did
+pub fn execute_resolve_pending_tx(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    channel_id: String,
+    seq_id: u64,
+    outcome: PendingTxOutcome,
+    force: bool,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let payload = read_sudo_payload(deps.storage, channel_id.clone(), seq_id)
+        .map_err(|_| ContractError::PendingTxNotExist {})?;
+
+    let pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
+    if info.sender != pool_info.admin {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+
+    // a late ack of a tx still within its timeout would otherwise be applied twice
+    match SUDO_PAYLOAD_DEADLINE.may_load(deps.storage, (channel_id.clone(), seq_id))? {
+        Some(deadline) => {
+            if env.block.time.seconds() <= deadline {
+                return Err(ContractError::PendingTxNotExpired {}.into());
+            }
+        }
+        None => {
+            if !force {
+                return Err(ContractError::PendingTxDeadlineUnknown {}.into());
+            }
+        }
+    }
+
+    remove_sudo_payload(deps.storage, channel_id.clone(), seq_id);
+
+    let pool_addr = payload.pool_addr.clone();
+    let (resp, outcome_str) = match outcome {
+        PendingTxOutcome::Success => (sudo_callback(deps.into_empty(), env, payload)?, "success"),
+        PendingTxOutcome::Failed => (sudo_failed_callback(deps.into_empty(), payload)?, "failed"),
+    };
+
+    Ok(resp
+        .add_attribute("action", "resolve_pending_tx")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("channel_id", channel_id)
+        .add_attribute("seq_id", seq_id.to_string())
+        .add_attribute("outcome", outcome_str))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{
+        save_sudo_payload, tests::BASELINE_POOL_INFO, EraStatus, PoolInfo, SudoData, SudoPayload,
+        TxType, SUDO_PAYLOAD_VERSION,
+    };
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_json, OwnedDeps, StdResult};
+    use std::marker::PhantomData;
+
+    const POOL_ADDR: &str = "pool_ica_addr";
+    const CHANNEL_ID: &str = "channel-1";
+
+    fn setup(
+        deadline: Option<u64>,
+    ) -> StdResult<OwnedDeps<MockStorage, MockApi, MockQuerier<NeutronQuery>, NeutronQuery>> {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: MockQuerier::<NeutronQuery>::new(&[]),
+            custom_query_type: PhantomData,
+        };
+        let pool_info: PoolInfo = from_json(BASELINE_POOL_INFO)?;
+        POOLS.save(deps.as_mut().storage, POOL_ADDR.to_string(), &pool_info)?;
+        save_sudo_payload(
+            deps.as_mut().storage,
+            CHANNEL_ID.to_string(),
+            7,
+            SudoPayload {
+                version: SUDO_PAYLOAD_VERSION,
+                data: SudoData::SetWithdrawAddr {
+                    withdraw_addr: "withdraw_ica_addr".to_string(),
+                },
+                pool_addr: POOL_ADDR.to_string(),
+                port_id: "icacontroller-stake_manager.ica".to_string(),
+                tx_type: TxType::SetWithdrawAddr,
+            },
+        )?;
+        if let Some(deadline) = deadline {
+            SUDO_PAYLOAD_DEADLINE.save(
+                deps.as_mut().storage,
+                (CHANNEL_ID.to_string(), 7),
+                &deadline,
+            )?;
+        }
+        Ok(deps)
+    }
+
+    fn resolve(deps: DepsMut<NeutronQuery>, env: Env, force: bool) -> NeutronResult<()> {
+        execute_resolve_pending_tx(
+            deps,
+            env,
+            mock_info("admin", &[]),
+            CHANNEL_ID.to_string(),
+            7,
+            PendingTxOutcome::Failed,
+            force,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_waits_for_the_recorded_deadline() -> NeutronResult<()> {
+        let mut env = mock_env();
+        let mut deps = setup(Some(env.block.time.seconds() + 10))?;
+
+        env.block.time = env.block.time.plus_seconds(10);
+        assert!(resolve(deps.as_mut(), env.clone(), true).is_err());
+
+        env.block.time = env.block.time.plus_seconds(1);
+        resolve(deps.as_mut(), env, false)?;
+        let pool_info = POOLS.load(&deps.storage, POOL_ADDR.to_string())?;
+        assert_eq!(pool_info.status, EraStatus::InitFailed);
+        assert!(read_sudo_payload(&deps.storage, CHANNEL_ID.to_string(), 7).is_err());
+        assert!(SUDO_PAYLOAD_DEADLINE
+            .may_load(&deps.storage, (CHANNEL_ID.to_string(), 7))?
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_without_deadline_requires_force() -> NeutronResult<()> {
+        let mut deps = setup(None)?;
+
+        assert!(resolve(deps.as_mut(), mock_env(), false).is_err());
+        resolve(deps.as_mut(), mock_env(), true)?;
+        assert!(read_sudo_payload(&deps.storage, CHANNEL_ID.to_string(), 7).is_err());
+        Ok(())
+    }
+}
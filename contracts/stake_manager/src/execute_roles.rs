@@ -0,0 +1,172 @@
+use crate::error_conversion::ContractError;
+use crate::msg::ConfigPoolParams;
+use crate::state::{PoolInfo, Role, POOLS, POOL_ROLES};
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Order, Response, StdResult, Storage};
+use neutron_sdk::{
+    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    NeutronResult,
+};
+
+pub fn has_role(
+    storage: &dyn Storage,
+    pool_addr: String,
+    account: &Addr,
+    role: &Role,
+) -> StdResult<bool> {
+    Ok(POOL_ROLES
+        .may_load(storage, (pool_addr, account.clone()))?
+        .map_or(false, |roles| roles.contains(role)))
+}
+
+// the pool admin holds every role
+pub fn check_pool_role(
+    storage: &dyn Storage,
+    pool_info: &PoolInfo,
+    pool_addr: String,
+    sender: &Addr,
+    role: &Role,
+) -> NeutronResult<()> {
+    if *sender == pool_info.admin || has_role(storage, pool_addr, sender, role)? {
+        return Ok(());
+    }
+
+    Err(ContractError::Unauthorized {}.into())
+}
+
+// era steps stay permissionless until an era operator is granted
+pub fn check_era_operator(
+    storage: &dyn Storage,
+    pool_info: &PoolInfo,
+    pool_addr: String,
+    sender: &Addr,
+) -> NeutronResult<()> {
+    let restricted = POOL_ROLES
+        .prefix(pool_addr.clone())
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, roles)| roles.contains(&Role::EraOperator)))
+        .collect::<StdResult<Vec<bool>>>()?
+        .contains(&true);
+    if !restricted {
+        return Ok(());
+    }
+
+    check_pool_role(storage, pool_info, pool_addr, sender, &Role::EraOperator)
+}
+
+// a pauser may only set paused and a fee manager only the fee fields, the rest needs the admin
+pub fn check_config_pool_roles(
+    storage: &dyn Storage,
+    pool_info: &PoolInfo,
+    sender: &Addr,
+    param: &ConfigPoolParams,
+) -> NeutronResult<()> {
+    if *sender == pool_info.admin {
+        return Ok(());
+    }
+
+    let admin_only = ConfigPoolParams {
+        pool_addr: String::new(),
+        paused: None,
+        platform_fee_receiver: None,
+        platform_fee_commission: None,
+        unbond_commission: None,
+        unbond_commission_schedule: None,
+        instant_unstake_fee_min: None,
+        instant_unstake_fee_max: None,
+        withdraw_for_tip: None,
+        fee_mode: None,
+        ..param.clone()
+    };
+    if admin_only != ConfigPoolParams::default() {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+
+    if param.paused.is_some() {
+        check_pool_role(
+            storage,
+            pool_info,
+            param.pool_addr.clone(),
+            sender,
+            &Role::Pauser,
+        )?;
+    }
+    let fee_only = ConfigPoolParams {
+        pool_addr: String::new(),
+        paused: None,
+        ..param.clone()
+    };
+    if fee_only != ConfigPoolParams::default() {
+        check_pool_role(
+            storage,
+            pool_info,
+            param.pool_addr.clone(),
+            sender,
+            &Role::FeeManager,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn execute_grant_role(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    pool_addr: String,
+    account: Addr,
+    role: Role,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    if info.sender != pool_info.admin {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+    deps.api.addr_validate(account.as_str())?;
+
+    let key = (pool_addr.clone(), account.clone());
+    let mut roles = POOL_ROLES
+        .may_load(deps.storage, key.clone())?
+        .unwrap_or_default();
+    if roles.contains(&role) {
+        return Err(ContractError::RoleAlreadyGranted {}.into());
+    }
+    roles.push(role.clone());
+    POOL_ROLES.save(deps.storage, key, &roles)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "grant_role")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("account", account)
+        .add_attribute("role", role.to_string()))
+}
+
+pub fn execute_revoke_role(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    pool_addr: String,
+    account: Addr,
+    role: Role,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    if info.sender != pool_info.admin {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+
+    let key = (pool_addr.clone(), account.clone());
+    let mut roles = POOL_ROLES
+        .may_load(deps.storage, key.clone())?
+        .unwrap_or_default();
+    if !roles.contains(&role) {
+        return Err(ContractError::RoleNotGranted {}.into());
+    }
+    roles.retain(|r| *r != role);
+    if roles.is_empty() {
+        POOL_ROLES.remove(deps.storage, key);
+    } else {
+        POOL_ROLES.save(deps.storage, key, &roles)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("account", account)
+        .add_attribute("role", role.to_string()))
+}
@@ -1,18 +1,21 @@
-use std::ops::{Add, Div, Mul};
+use std::ops::{Add, Div, Mul, Sub};
 use std::vec;
 
-use cosmwasm_std::{to_json_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg,
+};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
 };
 
-use crate::state::POOLS;
+use crate::state::{PoolInfo, SimulateStakeResponse, POOLS};
+use crate::unbond_commission::record_stake;
 use crate::{error_conversion::ContractError, helper::CAL_BASE};
 
 pub fn execute_stake(
     deps: DepsMut<NeutronQuery>,
-    _: Env,
+    env: Env,
     neutron_address: String,
     pool_addr: String,
     info: MessageInfo,
@@ -26,15 +29,16 @@ pub fn execute_stake(
         return Err(ContractError::ParamsErrorFundsNotMatch {}.into());
     }
 
-    let token_amount = info.funds[0].amount;
-    if token_amount < pool_info.minimal_stake {
-        return Err(ContractError::LessThanMinimalStake {}.into());
-    }
+    let SimulateStakeResponse {
+        token_amount,
+        buffer_amount,
+        bond_amount,
+        lsd_token_amount,
+    } = simulate_stake(&pool_info, info.funds[0].amount)?;
 
     pool_info.active = pool_info.active.add(token_amount);
-    pool_info.bond = pool_info.bond.add(token_amount);
-
-    let lsd_token_amount = token_amount.mul(CAL_BASE).div(pool_info.rate);
+    pool_info.bond = pool_info.bond.add(bond_amount);
+    pool_info.liquidity_buffer = pool_info.liquidity_buffer.add(buffer_amount);
 
     let msg = WasmMsg::Execute {
         contract_addr: pool_info.lsd_token.to_string(),
@@ -47,6 +51,13 @@ pub fn execute_stake(
         funds: vec![],
     };
     pool_info.total_lsd_token_amount = pool_info.total_lsd_token_amount.add(lsd_token_amount);
+    record_stake(
+        deps.storage,
+        deps.api.addr_validate(&neutron_address)?,
+        pool_addr.clone(),
+        lsd_token_amount,
+        env.block.time.seconds(),
+    )?;
 
     POOLS.save(deps.storage, pool_addr.clone(), &pool_info)?;
 
@@ -58,3 +69,33 @@ pub fn execute_stake(
         .add_attribute("token_amount", token_amount)
         .add_attribute("lsd_token_amount", lsd_token_amount))
 }
+
+// amounts of a stake, shared by execute_stake and the SimulateStake query
+pub fn simulate_stake(
+    pool_info: &PoolInfo,
+    token_amount: Uint128,
+) -> NeutronResult<SimulateStakeResponse> {
+    if pool_info.paused {
+        return Err(ContractError::PoolIsPaused {}.into());
+    }
+    if token_amount < pool_info.minimal_stake {
+        return Err(ContractError::LessThanMinimalStake {}.into());
+    }
+
+    // keep a share of the deposit for instant unstake until the buffer reaches its target
+    let buffer_amount = token_amount
+        .mul(pool_info.liquidity_buffer_ratio)
+        .div(CAL_BASE)
+        .min(
+            pool_info
+                .liquidity_buffer_target
+                .saturating_sub(pool_info.liquidity_buffer),
+        );
+
+    Ok(SimulateStakeResponse {
+        token_amount,
+        buffer_amount,
+        bond_amount: token_amount.sub(buffer_amount),
+        lsd_token_amount: token_amount.mul(CAL_BASE).div(pool_info.rate),
+    })
+}
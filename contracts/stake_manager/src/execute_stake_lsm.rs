@@ -1,13 +1,16 @@
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::{
     error_conversion::ContractError,
-    helper::DEFAULT_TIMEOUT_SECONDS,
+    helper::tx_timeout_seconds,
     helper::{min_ntrn_ibc_fee, query_denom_trace_from_ibc_denom, CAL_BASE},
     query::query_validator_by_addr,
     state::{EraStatus, SudoPayload, TxType, ValidatorUpdateStatus, INFO_OF_ICA_ID, POOLS},
     tx_callback::msg_with_sudo_callback,
+    unbond_commission::record_stake,
 };
 use cosmwasm_std::{
-    coins, to_json_binary, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, BankMsg, Coin, DepsMut, Env, MessageInfo, Response, Uint128,
+    WasmMsg,
 };
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
@@ -111,7 +114,8 @@ pub fn execute_stake_lsm(
                 revision_number: None,
                 revision_height: None,
             },
-            timeout_timestamp: env.block.time.nanos() + DEFAULT_TIMEOUT_SECONDS * 1_000_000_000,
+            timeout_timestamp: env.block.time.nanos()
+                + tx_timeout_seconds(&pool_info, &TxType::StakeLsm) * 1_000_000_000,
             memo: "".to_string(),
             fee: fee.clone(),
         };
@@ -121,15 +125,14 @@ pub fn execute_stake_lsm(
             transfer_share_token_msg,
             SudoPayload {
                 port_id: pool_ica_info.ctrl_port_id,
-                // the acknowledgement later
-                message: format!(
-                    "{}_{}_{}_{}_{}",
-                    neutron_address,
+                version: SUDO_PAYLOAD_VERSION,
+                data: SudoData::StakeLsm {
+                    staker: neutron_address,
                     token_amount,
                     share_token_amount,
-                    share_token_ibc_denom.clone(),
-                    share_token_denom.clone(),
-                ),
+                    share_token_ibc_denom: share_token_ibc_denom.clone(),
+                    share_token_denom: share_token_denom.clone(),
+                },
                 pool_addr: pool_addr.clone(),
                 tx_type: TxType::StakeLsm,
             },
@@ -143,35 +146,23 @@ pub fn execute_stake_lsm(
 
 pub fn sudo_stake_lsm_callback(
     deps: DepsMut,
+    env: Env,
     payload: SudoPayload,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    let parts: Vec<String> = payload.message.split('_').map(String::from).collect();
-    if parts.len() != 5 {
-        return Err(ContractError::UnsupportedMessage(payload.message).into());
-    }
-
-    let staker_neutron_addr = parts.get(0).unwrap();
-    let token_amount_str = parts.get(1).unwrap();
-    let share_token_amount_str = parts.get(2).unwrap();
-    let share_token_denom = parts.get(4).unwrap();
-
-    let token_amount = match token_amount_str.parse::<u128>() {
-        Ok(amount) => amount,
-        Err(_) => {
-            return Err(ContractError::UnsupportedMessage(payload.message).into());
-        }
-    };
-    let share_token_amount = match share_token_amount_str.parse::<u128>() {
-        Ok(amount) => amount,
-        Err(_) => {
-            return Err(ContractError::UnsupportedMessage(payload.message).into());
-        }
+    let SudoData::StakeLsm {
+        staker: staker_neutron_addr,
+        token_amount: token_amount_use,
+        share_token_amount,
+        share_token_denom,
+        ..
+    } = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
     };
 
     let mut pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
 
     // cal
-    let token_amount_use = Uint128::new(token_amount);
     pool_info.active = pool_info.active.add(token_amount_use);
     let lsd_token_amount = token_amount_use.mul(CAL_BASE).div(pool_info.rate);
 
@@ -187,10 +178,17 @@ pub fn sudo_stake_lsm_callback(
         funds: vec![],
     };
     pool_info.total_lsd_token_amount = pool_info.total_lsd_token_amount.add(lsd_token_amount);
+    record_stake(
+        deps.storage,
+        Addr::unchecked(staker_neutron_addr.clone()),
+        payload.pool_addr.clone(),
+        lsd_token_amount,
+        env.block.time.seconds(),
+    )?;
 
     pool_info.share_tokens.push(Coin {
-        denom: share_token_denom.to_string(),
-        amount: Uint128::new(share_token_amount),
+        denom: share_token_denom,
+        amount: share_token_amount,
     });
 
     // pool_info.share_tokens
@@ -209,25 +207,19 @@ pub fn sudo_stake_lsm_failed_callback(
     _: DepsMut,
     payload: SudoPayload,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    let parts: Vec<String> = payload.message.split('_').map(String::from).collect();
-    if parts.len() != 5 {
-        return Err(ContractError::UnsupportedMessage(payload.message).into());
-    }
-
-    let staker_neutron_addr = parts.get(0).unwrap();
-    let share_token_amount_str = parts.get(2).unwrap();
-    let share_token_ibc_denom = parts.get(3).unwrap();
-
-    let share_token_amount = match share_token_amount_str.parse::<u128>() {
-        Ok(amount) => amount,
-        Err(_) => {
-            return Err(ContractError::UnsupportedMessage(payload.message).into());
-        }
+    let SudoData::StakeLsm {
+        staker: staker_neutron_addr,
+        share_token_amount,
+        share_token_ibc_denom,
+        ..
+    } = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
     };
 
     let msg = BankMsg::Send {
-        to_address: staker_neutron_addr.to_string(),
-        amount: coins(share_token_amount, share_token_ibc_denom),
+        to_address: staker_neutron_addr,
+        amount: coins(share_token_amount.u128(), share_token_ibc_denom),
     };
 
     Ok(Response::new().add_message(msg))
@@ -1,20 +1,27 @@
 use std::ops::{Add, Div, Mul, Sub};
 use std::vec;
 
-use cosmwasm_std::{to_json_binary, CosmosMsg, DepsMut, MessageInfo, Response, Uint128, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, Storage, Uint128, WasmMsg,
+};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
 };
 
+use crate::execute_withdraw::unstake_withdraw_era;
+use crate::helper::{gen_unstake_nft_msg, unstake_nft_token_id};
 use crate::state::{
-    UnstakeInfo, WithdrawStatus, POOLS, UNSTAKES_INDEX_FOR_USER, UNSTAKES_OF_INDEX,
+    unstakes_of_index, PoolInfo, SimulateUnstakeResponse, UnstakeInfo, WithdrawStatus, POOLS,
+    UNSTAKES_INDEX_FOR_USER,
 };
+use crate::unbond_commission::{consume_stake_record, unbond_fee_of};
 use crate::{error_conversion::ContractError, helper::CAL_BASE};
 
 // Before this step, need the user to authorize burn from
 pub fn execute_unstake(
     deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     lsd_token_amount: Uint128,
     pool_addr: String,
@@ -37,36 +44,42 @@ pub fn execute_unstake(
     }
 
     let mut rsp = Response::new();
-    // cal fee
-    let mut will_burn_lsd_token_amount = lsd_token_amount;
-    if pool_info.unbond_commission > Uint128::zero() {
-        let cms_fee = lsd_token_amount
-            .mul(pool_info.unbond_commission)
-            .div(CAL_BASE);
-        will_burn_lsd_token_amount = lsd_token_amount.sub(cms_fee);
-
-        if cms_fee.u128() > 0 {
-            let mint_msg = WasmMsg::Execute {
-                contract_addr: pool_info.lsd_token.to_string(),
-                msg: to_json_binary(
-                    &(lsd_token::msg::ExecuteMsg::TransferFrom {
-                        owner: info.sender.to_string(),
-                        recipient: pool_info.platform_fee_receiver.to_string(),
-                        amount: cms_fee,
-                    }),
-                )?,
-                funds: vec![],
-            };
-
-            rsp = rsp.add_message(mint_msg);
-        }
-    }
-    if will_burn_lsd_token_amount.is_zero() {
-        return Err(ContractError::BurnLsdTokenAmountIsZero {}.into());
+    let SimulateUnstakeResponse {
+        commission,
+        fee: cms_fee,
+        burn_lsd_token_amount: will_burn_lsd_token_amount,
+        token_amount,
+        receive_amount,
+        ..
+    } = simulate_unstake(
+        deps.storage,
+        &pool_info,
+        pool_addr.clone(),
+        info.sender.clone(),
+        lsd_token_amount,
+        env.block.time.seconds(),
+    )?;
+    if !cms_fee.is_zero() {
+        let mint_msg = WasmMsg::Execute {
+            contract_addr: pool_info.lsd_token.to_string(),
+            msg: to_json_binary(
+                &(lsd_token::msg::ExecuteMsg::TransferFrom {
+                    owner: info.sender.to_string(),
+                    recipient: pool_info.platform_fee_receiver.to_string(),
+                    amount: cms_fee,
+                }),
+            )?,
+            funds: vec![],
+        };
+
+        rsp = rsp.add_message(mint_msg);
     }
-
-    // Calculate the number of tokens(atom)
-    let token_amount = will_burn_lsd_token_amount.mul(pool_info.rate).div(CAL_BASE);
+    consume_stake_record(
+        deps.storage,
+        info.sender.clone(),
+        pool_addr.clone(),
+        lsd_token_amount,
+    )?;
 
     // update pool info
     pool_info.next_unstake_index += 1;
@@ -88,16 +101,6 @@ pub fn execute_unstake(
         .total_lsd_token_amount
         .sub(will_burn_lsd_token_amount);
 
-    // fix precision issues
-    let receive_amount = if token_amount > Uint128::new(5) {
-        token_amount.sub(Uint128::new(5))
-    } else {
-        Uint128::zero()
-    };
-    if receive_amount.is_zero() {
-        return Err(ContractError::EncodeErrZeroWithdrawAmount {}.into());
-    }
-
     // update unstake info
     let will_use_unstake_index = pool_info.next_unstake_index;
     let unstake_info = UnstakeInfo {
@@ -107,11 +110,29 @@ pub fn execute_unstake(
         amount: receive_amount,
         status: WithdrawStatus::Default,
         index: will_use_unstake_index,
+        nft: pool_info.unstake_nft.clone(),
+        transfer_deadline: None,
     };
 
+    if let Some(unstake_nft) = pool_info.unstake_nft.clone() {
+        let mint_nft_msg = gen_unstake_nft_msg(
+            &unstake_nft,
+            &unstake_nft::msg::ExecuteMsg::Mint {
+                token_id: unstake_nft_token_id(&pool_addr, will_use_unstake_index),
+                owner: info.sender.to_string(),
+                token_uri: None,
+                extension: unstake_nft::msg::UnstakeClaim {
+                    pool_addr: pool_addr.clone(),
+                    unstake_index: will_use_unstake_index,
+                },
+            },
+        )?;
+        rsp = rsp.add_message(mint_nft_msg);
+    }
+
     unstakes_index_for_user.push(will_use_unstake_index);
 
-    UNSTAKES_OF_INDEX.save(
+    unstakes_of_index().save(
         deps.storage,
         (pool_addr.clone(), will_use_unstake_index),
         &unstake_info,
@@ -131,5 +152,51 @@ pub fn execute_unstake(
         .add_attribute("from", info.sender.to_string())
         .add_attribute("token_amount", token_amount.to_string())
         .add_attribute("lsd_token_amount", lsd_token_amount.to_string())
+        .add_attribute("unbond_commission", commission.to_string())
+        .add_attribute("unbond_fee", cms_fee.to_string())
         .add_attribute("unstake_index", will_use_unstake_index.to_string()))
 }
+
+// amounts of an unstake, shared by execute_unstake and the SimulateUnstake query
+pub fn simulate_unstake(
+    storage: &dyn Storage,
+    pool_info: &PoolInfo,
+    pool_addr: String,
+    user: Addr,
+    lsd_token_amount: Uint128,
+    now: u64,
+) -> NeutronResult<SimulateUnstakeResponse> {
+    if lsd_token_amount.is_zero() {
+        return Err(ContractError::EncodeErrLsdTokenAmountZero {}.into());
+    }
+
+    let (held_seconds, commission, fee) =
+        unbond_fee_of(storage, pool_info, user, pool_addr, lsd_token_amount, now)?;
+    let burn_lsd_token_amount = lsd_token_amount.sub(fee);
+    if burn_lsd_token_amount.is_zero() {
+        return Err(ContractError::BurnLsdTokenAmountIsZero {}.into());
+    }
+
+    // Calculate the number of tokens(atom)
+    let token_amount = burn_lsd_token_amount.mul(pool_info.rate).div(CAL_BASE);
+
+    // fix precision issues
+    let receive_amount = if token_amount > Uint128::new(5) {
+        token_amount.sub(Uint128::new(5))
+    } else {
+        Uint128::zero()
+    };
+    if receive_amount.is_zero() {
+        return Err(ContractError::EncodeErrZeroWithdrawAmount {}.into());
+    }
+
+    Ok(SimulateUnstakeResponse {
+        held_seconds,
+        commission,
+        fee,
+        burn_lsd_token_amount,
+        token_amount,
+        receive_amount,
+        withdraw_era: unstake_withdraw_era(pool_info, pool_info.era),
+    })
+}
@@ -1,11 +1,17 @@
-use crate::helper::{check_ibc_fee, gen_msg_send};
+use crate::error_conversion::ContractError;
+use crate::helper::{
+    check_ibc_fee, check_ica_channel_open, gen_msg_send, gen_msg_transfer, gen_unstake_nft_msg,
+    tx_timeout_seconds, unstake_nft_token_id,
+};
 use crate::state::{
-    SudoPayload, TxType, WithdrawStatus, INFO_OF_ICA_ID, POOLS, UNSTAKES_INDEX_FOR_USER,
-    UNSTAKES_OF_INDEX,
+    unstakes_of_index, PoolInfo, SimulateWithdrawResponse, SudoPayload, TransferOutcome, TxType,
+    UnstakeInfo, WithdrawMode, WithdrawStatus, INFO_OF_ICA_ID, POOLS, UNSTAKES_INDEX_FOR_USER,
 };
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::tx_callback::msg_with_sudo_callback;
-use crate::{error_conversion::ContractError, helper::DEFAULT_TIMEOUT_SECONDS};
-use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response, Uint128};
+use cosmwasm_std::{
+    Addr, DepsMut, Env, MessageInfo, QuerierWrapper, Response, Storage, Uint128, WasmMsg,
+};
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
     NeutronResult,
@@ -14,72 +20,73 @@ use std::vec;
 
 pub fn execute_withdraw(
     mut deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     pool_addr: String,
     receiver: Addr,
     unstake_index_list: Vec<u64>,
+    mode: WithdrawMode,
 ) -> NeutronResult<Response<NeutronMsg>> {
     if unstake_index_list.is_empty() {
         return Err(ContractError::EmptyUnstakeList {}.into());
     }
 
     let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
-
-    let mut total_withdraw_amount = Uint128::zero();
-    for unstake_index in unstake_index_list.clone() {
-        let mut unstake_info =
-            UNSTAKES_OF_INDEX.load(deps.storage, (pool_addr.clone(), unstake_index))?;
-
-        if unstake_info.pool_addr != pool_addr {
-            return Err(ContractError::UnstakeIndexPoolNotMatch(unstake_index).into());
-        }
-
-        if unstake_info.unstaker != info.sender {
-            return Err(ContractError::UnstakeIndexUnstakerNotMatch(unstake_index).into());
-        }
-
-        if unstake_info.status == WithdrawStatus::Pending {
-            return Err(ContractError::UnstakeIndexStatusNotMatch(unstake_index).into());
-        }
-        if unstake_info.era + pool_info.unbonding_period > pool_info.era {
-            return Err(ContractError::UnstakeIndexNotWithdrawable(unstake_index).into());
+    if mode == WithdrawMode::Neutron {
+        if pool_info.host_transfer_channel_id.is_empty() {
+            return Err(ContractError::HostTransferChannelNotSet {}.into());
         }
-
-        // Remove the unstake index element of info.sender from UNSTAKES_INDEX_FOR_USER
-        total_withdraw_amount += unstake_info.amount;
-
-        unstake_info.status = WithdrawStatus::Pending;
-        UNSTAKES_OF_INDEX.save(
-            deps.storage,
-            (pool_addr.clone(), unstake_index),
-            &unstake_info,
-        )?;
+        deps.api.addr_validate(receiver.as_str())?;
     }
 
+    let (total_withdraw_amount, lock_msgs) = pending_withdraw_amount(
+        deps.branch(),
+        &pool_info,
+        pool_addr.clone(),
+        &info.sender,
+        &unstake_index_list,
+    )?;
+
     if total_withdraw_amount.is_zero() {
         return Err(ContractError::EncodeErrZeroWithdrawAmount {}.into());
     }
 
-    let unstake_index_list_str = unstake_index_list
-        .iter()
-        .map(|index| index.to_string())
-        .collect::<Vec<String>>()
-        .join("_");
-
     // interchain tx send atom
     let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
     let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
-    let cosmos_msg = NeutronMsg::submit_tx(
-        pool_ica_info.ctrl_connection_id.clone(),
-        pool_info.ica_id.clone(),
-        vec![gen_msg_send(
+    check_ica_channel_open(&pool_ica_info)?;
+    let tx_type = match mode {
+        WithdrawMode::HostChain => TxType::UserWithdraw,
+        WithdrawMode::Neutron => TxType::UserWithdrawToNeutron,
+    };
+    let timeout_seconds = tx_timeout_seconds(&pool_info, &tx_type);
+    let transfer_deadline = match mode {
+        WithdrawMode::HostChain => 0,
+        WithdrawMode::Neutron => env.block.time.seconds() + timeout_seconds,
+    };
+    let withdraw_msg = match mode {
+        WithdrawMode::HostChain => gen_msg_send(
             pool_addr.clone(),
             receiver.to_string(),
-            pool_info.remote_denom,
+            pool_info.remote_denom.clone(),
             total_withdraw_amount.to_string(),
-        )?],
+        )?,
+        // the transfer module refunds the pool ica if the transfer to neutron fails
+        WithdrawMode::Neutron => gen_msg_transfer(
+            pool_info.host_transfer_channel_id.clone(),
+            pool_addr.clone(),
+            receiver.to_string(),
+            pool_info.remote_denom.clone(),
+            total_withdraw_amount.to_string(),
+            transfer_deadline * 1_000_000_000,
+        )?,
+    };
+    let cosmos_msg = NeutronMsg::submit_tx(
+        pool_ica_info.ctrl_connection_id.clone(),
+        pool_info.ica_id.clone(),
+        vec![withdraw_msg],
         "".to_string(),
-        DEFAULT_TIMEOUT_SECONDS,
+        timeout_seconds,
         ibc_fee,
     );
 
@@ -90,64 +97,238 @@ pub fn execute_withdraw(
         cosmos_msg,
         SudoPayload {
             port_id: pool_ica_info.ctrl_port_id,
-            message: format!(
-                "{}_{}_{}_{}",
-                total_withdraw_amount, info.sender, receiver, unstake_index_list_str
-            ),
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::UserWithdraw {
+                amount: total_withdraw_amount,
+                user: info.sender.to_string(),
+                receiver: receiver.to_string(),
+                unstake_index_list,
+                transfer_deadline,
+            },
             pool_addr: pool_addr.clone(),
-            tx_type: TxType::UserWithdraw,
+            tx_type,
         },
     )?;
 
-    Ok(Response::new().add_submessage(submsg))
+    Ok(Response::new()
+        .add_messages(lock_msgs)
+        .add_submessage(submsg))
+}
+
+// check the owner may withdraw the unstake indexes, mark them pending and lock their nfts
+pub fn pending_withdraw_amount(
+    deps: DepsMut<NeutronQuery>,
+    pool_info: &PoolInfo,
+    pool_addr: String,
+    owner_addr: &Addr,
+    unstake_index_list: &[u64],
+) -> NeutronResult<(Uint128, Vec<WasmMsg>)> {
+    let mut total_withdraw_amount = Uint128::zero();
+    let mut lock_msgs = vec![];
+    for unstake_index in unstake_index_list.iter().copied() {
+        let mut unstake_info =
+            unstakes_of_index().load(deps.storage, (pool_addr.clone(), unstake_index))?;
+        check_unstake_withdrawable(
+            &deps.querier,
+            pool_info,
+            &pool_addr,
+            owner_addr,
+            &unstake_info,
+        )?;
+
+        // Remove the unstake index element of info.sender from UNSTAKES_INDEX_FOR_USER
+        total_withdraw_amount += unstake_info.amount;
+
+        unstake_info.status = WithdrawStatus::Pending;
+        unstakes_of_index().save(
+            deps.storage,
+            (pool_addr.clone(), unstake_index),
+            &unstake_info,
+        )?;
+
+        if let Some(unstake_nft) = unstake_info.nft {
+            lock_msgs.push(gen_unstake_nft_msg(
+                &unstake_nft,
+                &unstake_nft::msg::ExecuteMsg::Lock {
+                    token_id: unstake_nft_token_id(&pool_addr, unstake_index),
+                },
+            )?);
+        }
+    }
+
+    Ok((total_withdraw_amount, lock_msgs))
+}
+
+// era from which an unstake made in unstake_era can be withdrawn
+pub fn unstake_withdraw_era(pool_info: &PoolInfo, unstake_era: u64) -> u64 {
+    unstake_era + pool_info.unbonding_period
+}
+
+pub fn check_unstake_withdrawable(
+    querier: &QuerierWrapper<NeutronQuery>,
+    pool_info: &PoolInfo,
+    pool_addr: &str,
+    owner_addr: &Addr,
+    unstake_info: &UnstakeInfo,
+) -> NeutronResult<()> {
+    check_unstake_owner(querier, pool_addr, owner_addr, unstake_info)?;
+
+    if unstake_info.status != WithdrawStatus::Default {
+        return Err(ContractError::UnstakeIndexStatusNotMatch(unstake_info.index).into());
+    }
+    if unstake_withdraw_era(pool_info, unstake_info.era) > pool_info.era {
+        return Err(ContractError::UnstakeIndexNotWithdrawable(unstake_info.index).into());
+    }
+
+    Ok(())
+}
+
+// the nft holder owns the unstake if it has one, otherwise the unstaker
+pub fn check_unstake_owner(
+    querier: &QuerierWrapper<NeutronQuery>,
+    pool_addr: &str,
+    owner_addr: &Addr,
+    unstake_info: &UnstakeInfo,
+) -> NeutronResult<()> {
+    let unstake_index = unstake_info.index;
+    if unstake_info.pool_addr != pool_addr {
+        return Err(ContractError::UnstakeIndexPoolNotMatch(unstake_index).into());
+    }
+
+    match unstake_info.nft.clone() {
+        Some(unstake_nft) => {
+            let owner: unstake_nft::msg::OwnerOfResponse = querier.query_wasm_smart(
+                unstake_nft,
+                &unstake_nft::msg::QueryMsg::OwnerOf {
+                    token_id: unstake_nft_token_id(pool_addr, unstake_index),
+                    include_expired: None,
+                },
+            )?;
+            if owner.owner != *owner_addr {
+                return Err(ContractError::UnstakeIndexNftOwnerNotMatch(unstake_index).into());
+            }
+        }
+        None => {
+            if unstake_info.unstaker != *owner_addr {
+                return Err(ContractError::UnstakeIndexUnstakerNotMatch(unstake_index).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// shared with the withdraw executes, without a list it walks the unstakes made by the owner
+pub fn simulate_withdraw(
+    querier: &QuerierWrapper<NeutronQuery>,
+    storage: &dyn Storage,
+    pool_info: &PoolInfo,
+    pool_addr: String,
+    owner_addr: &Addr,
+    unstake_index_list: Option<Vec<u64>>,
+) -> NeutronResult<SimulateWithdrawResponse> {
+    let explicit = unstake_index_list.is_some();
+    let unstake_index_list = match unstake_index_list {
+        Some(unstake_index_list) => unstake_index_list,
+        None => UNSTAKES_INDEX_FOR_USER
+            .may_load(storage, (owner_addr.clone(), pool_addr.clone()))?
+            .unwrap_or_default(),
+    };
+
+    let mut rsp = SimulateWithdrawResponse {
+        amount: Uint128::zero(),
+        withdrawable_unstake_index_list: vec![],
+        earliest_withdraw_era: None,
+    };
+    for unstake_index in unstake_index_list.iter().copied() {
+        if rsp.withdrawable_unstake_index_list.contains(&unstake_index) {
+            if explicit {
+                return Err(ContractError::UnstakeIndexStatusNotMatch(unstake_index).into());
+            }
+            continue;
+        }
+        let unstake_info = unstakes_of_index().load(storage, (pool_addr.clone(), unstake_index))?;
+
+        let withdraw_era = unstake_withdraw_era(pool_info, unstake_info.era);
+        if unstake_info.status == WithdrawStatus::Default && withdraw_era > pool_info.era {
+            check_unstake_owner(querier, &pool_addr, owner_addr, &unstake_info)?;
+            rsp.earliest_withdraw_era = Some(
+                rsp.earliest_withdraw_era
+                    .map_or(withdraw_era, |era| era.min(withdraw_era)),
+            );
+            continue;
+        }
+
+        match check_unstake_withdrawable(querier, pool_info, &pool_addr, owner_addr, &unstake_info)
+        {
+            Ok(()) => {
+                rsp.amount += unstake_info.amount;
+                rsp.withdrawable_unstake_index_list.push(unstake_index);
+            }
+            // an explicit list fails the same way the execute would
+            Err(err) if explicit => return Err(err),
+            Err(_) => {}
+        }
+    }
+
+    Ok(rsp)
+}
+
+pub fn join_unstake_index_list(unstake_index_list: &[u64]) -> String {
+    unstake_index_list
+        .iter()
+        .map(|index| index.to_string())
+        .collect::<Vec<String>>()
+        .join("_")
 }
 
 pub fn sudo_withdraw_callback(
     deps: DepsMut,
     payload: SudoPayload,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    let parts: Vec<String> = payload.message.split('_').map(String::from).collect();
-    if parts.len() <= 3 {
-        return Err(ContractError::UnsupportedMessage(payload.message).into());
-    }
-    let total_withdraw_amount = parts.get(0).unwrap();
-    let user_addr = Addr::unchecked(parts.get(1).unwrap());
-    let receiver = parts.get(2).unwrap();
-    let unstake_index_list_str = parts
-        .iter()
-        .skip(3)
-        .cloned()
-        .collect::<Vec<String>>()
-        .join("_");
-
-    if let Some((_, index_list)) = parts.split_first() {
-        if let Some(mut unstakes) = UNSTAKES_INDEX_FOR_USER
-            .may_load(deps.storage, (user_addr.clone(), payload.pool_addr.clone()))?
-        {
-            unstakes.retain(|unstake_index| {
-                if index_list.contains(&unstake_index.to_string()) {
-                    UNSTAKES_OF_INDEX
-                        .remove(deps.storage, (payload.pool_addr.clone(), *unstake_index));
-                    return false;
-                }
+    let SudoData::UserWithdraw {
+        amount: total_withdraw_amount,
+        user: user_addr,
+        receiver,
+        unstake_index_list,
+        transfer_deadline,
+    } = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
 
-                true
-            });
-
-            UNSTAKES_INDEX_FOR_USER.save(
+    // the ack only says the transfer was sent, keep the unstakes until it is delivered or refunded
+    let (rsp, action) = if payload.tx_type == TxType::UserWithdrawToNeutron {
+        for index in unstake_index_list.iter().copied() {
+            let mut unstake_info =
+                unstakes_of_index().load(deps.storage, (payload.pool_addr.clone(), index))?;
+            unstake_info.status = WithdrawStatus::Transferring;
+            unstake_info.transfer_deadline = Some(transfer_deadline);
+            unstakes_of_index().save(
                 deps.storage,
-                (user_addr.clone(), payload.pool_addr.clone()),
-                &unstakes,
+                (payload.pool_addr.clone(), index),
+                &unstake_info,
             )?;
         }
-    }
+        (
+            Response::new().add_attribute("transfer_deadline", transfer_deadline.to_string()),
+            "withdraw_to_neutron",
+        )
+    } else {
+        let burn_msgs =
+            settle_withdrawn_unstakes(deps, payload.pool_addr.clone(), &unstake_index_list)?;
+        (Response::new().add_messages(burn_msgs), "withdraw")
+    };
 
-    Ok(Response::new()
-        .add_attribute("action", "withdraw")
+    Ok(rsp
+        .add_attribute("action", action)
         .add_attribute("from", user_addr)
         .add_attribute("pool", payload.pool_addr.clone())
         .add_attribute("receiver", receiver)
-        .add_attribute("unstake_index_list", unstake_index_list_str)
+        .add_attribute(
+            "unstake_index_list",
+            join_unstake_index_list(&unstake_index_list),
+        )
         .add_attribute("amount", total_withdraw_amount))
 }
 
@@ -155,22 +336,314 @@ pub fn sudo_withdraw_failed_callback(
     deps: DepsMut,
     payload: SudoPayload,
 ) -> NeutronResult<Response<NeutronMsg>> {
-    let parts: Vec<String> = payload.message.split('_').map(String::from).collect();
+    let (SudoData::UserWithdraw {
+        unstake_index_list, ..
+    }
+    | SudoData::WithdrawFor {
+        unstake_index_list, ..
+    }) = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
 
-    // skip withdraw_amount and user_addr and reviver
-    for index_str in parts.iter().skip(3) {
-        let index = index_str.parse::<u64>().unwrap();
-        let mut unstake_info =
-            UNSTAKES_OF_INDEX.load(deps.storage, (payload.pool_addr.clone(), index))?;
+    let unlock_msgs =
+        reset_withdrawing_unstakes(deps.storage, payload.pool_addr.clone(), &unstake_index_list)?;
+
+    Ok(Response::new().add_messages(unlock_msgs))
+}
+
+// make the unstake indexes withdrawable again and unlock their nfts
+fn reset_withdrawing_unstakes(
+    storage: &mut dyn Storage,
+    pool_addr: String,
+    unstake_index_list: &[u64],
+) -> NeutronResult<Vec<WasmMsg>> {
+    let mut unlock_msgs = vec![];
+    for index in unstake_index_list.iter().copied() {
+        let mut unstake_info = unstakes_of_index().load(storage, (pool_addr.clone(), index))?;
 
         unstake_info.status = WithdrawStatus::Default;
+        unstake_info.transfer_deadline = None;
 
-        UNSTAKES_OF_INDEX.save(
-            deps.storage,
-            (payload.pool_addr.clone(), index),
-            &unstake_info,
+        unstakes_of_index().save(storage, (pool_addr.clone(), index), &unstake_info)?;
+
+        if let Some(unstake_nft) = unstake_info.nft {
+            unlock_msgs.push(gen_unstake_nft_msg(
+                &unstake_nft,
+                &unstake_nft::msg::ExecuteMsg::Unlock {
+                    token_id: unstake_nft_token_id(&pool_addr, index),
+                },
+            )?);
+        }
+    }
+
+    Ok(unlock_msgs)
+}
+
+// settle a transfer to neutron once its deadline passed, a refund makes the unstakes withdrawable again.
+// the outcome is trusted from the pool admin, see ExecuteMsg::ResolveNeutronWithdraw
+pub fn execute_resolve_neutron_withdraw(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    pool_addr: String,
+    unstake_index_list: Vec<u64>,
+    outcome: TransferOutcome,
+) -> NeutronResult<Response<NeutronMsg>> {
+    if unstake_index_list.is_empty() {
+        return Err(ContractError::EmptyUnstakeList {}.into());
+    }
+
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    if info.sender != pool_info.admin {
+        return Err(ContractError::Unauthorized {}.into());
+    }
+
+    for index in unstake_index_list.iter().copied() {
+        let unstake_info = unstakes_of_index().load(deps.storage, (pool_addr.clone(), index))?;
+        if unstake_info.status != WithdrawStatus::Transferring {
+            return Err(ContractError::UnstakeIndexStatusNotMatch(index).into());
+        }
+        if env.block.time.seconds() <= unstake_info.transfer_deadline.unwrap_or_default() {
+            return Err(ContractError::UnstakeIndexTransferNotExpired(index).into());
+        }
+    }
+
+    let msgs = match outcome {
+        TransferOutcome::Delivered => {
+            settle_withdrawn_unstakes(deps.into_empty(), pool_addr.clone(), &unstake_index_list)?
+        }
+        TransferOutcome::Refunded => {
+            reset_withdrawing_unstakes(deps.storage, pool_addr.clone(), &unstake_index_list)?
+        }
+    };
+    let rsp = Response::new().add_messages(msgs);
+
+    Ok(rsp
+        .add_attribute("action", "resolve_neutron_withdraw")
+        .add_attribute("pool", pool_addr)
+        .add_attribute(
+            "unstake_index_list",
+            join_unstake_index_list(&unstake_index_list),
+        )
+        .add_attribute(
+            "outcome",
+            match outcome {
+                TransferOutcome::Delivered => "delivered",
+                TransferOutcome::Refunded => "refunded",
+            },
+        ))
+}
+
+// remove withdrawn unstake indexes and burn their receipts
+pub fn settle_withdrawn_unstakes(
+    deps: DepsMut,
+    pool_addr: String,
+    unstake_index_list: &[u64],
+) -> NeutronResult<Vec<WasmMsg>> {
+    let mut burn_msgs = vec![];
+    for index in unstake_index_list.iter().copied() {
+        let Some(unstake_info) =
+            unstakes_of_index().may_load(deps.storage, (pool_addr.clone(), index))?
+        else {
+            continue;
+        };
+
+        // the nft owner may differ from the unstaker holding the index
+        let unstaker = Addr::unchecked(unstake_info.unstaker.clone());
+        if let Some(mut unstakes) =
+            UNSTAKES_INDEX_FOR_USER.may_load(deps.storage, (unstaker.clone(), pool_addr.clone()))?
+        {
+            unstakes.retain(|unstake_index| *unstake_index != index);
+            UNSTAKES_INDEX_FOR_USER.save(deps.storage, (unstaker, pool_addr.clone()), &unstakes)?;
+        }
+        unstakes_of_index().remove(deps.storage, (pool_addr.clone(), index))?;
+
+        if let Some(unstake_nft) = unstake_info.nft {
+            burn_msgs.push(gen_unstake_nft_msg(
+                &unstake_nft,
+                &unstake_nft::msg::ExecuteMsg::Burn {
+                    token_id: unstake_nft_token_id(&pool_addr, index),
+                },
+            )?);
+        }
+    }
+
+    Ok(burn_msgs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::tests::BASELINE_POOL_INFO;
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_json, OwnedDeps, StdResult};
+    use std::marker::PhantomData;
+
+    const POOL_ADDR: &str = "pool_ica_addr";
+
+    fn setup() -> StdResult<OwnedDeps<MockStorage, MockApi, MockQuerier<NeutronQuery>, NeutronQuery>>
+    {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: MockQuerier::<NeutronQuery>::new(&[]),
+            custom_query_type: PhantomData,
+        };
+        let pool_info: PoolInfo = from_json(BASELINE_POOL_INFO)?;
+        POOLS.save(deps.as_mut().storage, POOL_ADDR.to_string(), &pool_info)?;
+        for index in [0, 1] {
+            unstakes_of_index().save(
+                deps.as_mut().storage,
+                (POOL_ADDR.to_string(), index),
+                &UnstakeInfo {
+                    era: 1,
+                    pool_addr: POOL_ADDR.to_string(),
+                    unstaker: "user".to_string(),
+                    amount: Uint128::new(10),
+                    status: WithdrawStatus::Pending,
+                    index,
+                    nft: None,
+                    transfer_deadline: None,
+                },
+            )?;
+        }
+        UNSTAKES_INDEX_FOR_USER.save(
+            deps.as_mut().storage,
+            (Addr::unchecked("user"), POOL_ADDR.to_string()),
+            &vec![0, 1],
         )?;
+        Ok(deps)
     }
 
-    Ok(Response::new())
+    fn neutron_withdraw_acked(
+        deps: DepsMut<NeutronQuery>,
+        transfer_deadline: u64,
+    ) -> NeutronResult<Response<NeutronMsg>> {
+        sudo_withdraw_callback(
+            deps.into_empty(),
+            SudoPayload {
+                version: SUDO_PAYLOAD_VERSION,
+                data: SudoData::UserWithdraw {
+                    amount: Uint128::new(20),
+                    user: "user".to_string(),
+                    receiver: "receiver".to_string(),
+                    unstake_index_list: vec![0, 1],
+                    transfer_deadline,
+                },
+                pool_addr: POOL_ADDR.to_string(),
+                port_id: "icacontroller-stake_manager.ica".to_string(),
+                tx_type: TxType::UserWithdrawToNeutron,
+            },
+        )
+    }
+
+    #[test]
+    fn neutron_withdraw_ack_keeps_unstakes_until_resolved() -> NeutronResult<()> {
+        let mut deps = setup()?;
+        let env = mock_env();
+        let deadline = env.block.time.seconds() + 100;
+
+        neutron_withdraw_acked(deps.as_mut(), deadline)?;
+        let unstake_info = unstakes_of_index().load(&deps.storage, (POOL_ADDR.to_string(), 0))?;
+        assert_eq!(unstake_info.status, WithdrawStatus::Transferring);
+        assert_eq!(unstake_info.transfer_deadline, Some(deadline));
+
+        let resolve = |deps: DepsMut<NeutronQuery>, env: Env, sender: &str| {
+            execute_resolve_neutron_withdraw(
+                deps,
+                env,
+                mock_info(sender, &[]),
+                POOL_ADDR.to_string(),
+                vec![0, 1],
+                TransferOutcome::Delivered,
+            )
+        };
+        assert!(resolve(deps.as_mut(), env.clone(), "admin").is_err());
+
+        let mut expired_env = env;
+        expired_env.block.time = expired_env.block.time.plus_seconds(101);
+        assert!(resolve(deps.as_mut(), expired_env.clone(), "user").is_err());
+
+        resolve(deps.as_mut(), expired_env, "admin")?;
+        assert!(unstakes_of_index()
+            .may_load(&deps.storage, (POOL_ADDR.to_string(), 0))?
+            .is_none());
+        assert!(UNSTAKES_INDEX_FOR_USER
+            .load(
+                &deps.storage,
+                (Addr::unchecked("user"), POOL_ADDR.to_string())
+            )?
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn refunded_neutron_withdraw_is_withdrawable_again() -> NeutronResult<()> {
+        let mut deps = setup()?;
+        let mut env = mock_env();
+
+        neutron_withdraw_acked(deps.as_mut(), env.block.time.seconds())?;
+        env.block.time = env.block.time.plus_seconds(1);
+        execute_resolve_neutron_withdraw(
+            deps.as_mut(),
+            env,
+            mock_info("admin", &[]),
+            POOL_ADDR.to_string(),
+            vec![0, 1],
+            TransferOutcome::Refunded,
+        )?;
+
+        for index in [0, 1] {
+            let unstake_info =
+                unstakes_of_index().load(&deps.storage, (POOL_ADDR.to_string(), index))?;
+            assert_eq!(unstake_info.status, WithdrawStatus::Default);
+            assert_eq!(unstake_info.transfer_deadline, None);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn failed_withdraw_unlocks_unstake_nft() -> NeutronResult<()> {
+        let mut deps = setup()?;
+        let mut unstake_info =
+            unstakes_of_index().load(&deps.storage, (POOL_ADDR.to_string(), 1))?;
+        unstake_info.nft = Some(Addr::unchecked("unstake_nft"));
+        unstakes_of_index().save(&mut deps.storage, (POOL_ADDR.to_string(), 1), &unstake_info)?;
+
+        let rsp = sudo_withdraw_failed_callback(
+            deps.as_mut().into_empty(),
+            SudoPayload {
+                version: SUDO_PAYLOAD_VERSION,
+                data: SudoData::UserWithdraw {
+                    amount: Uint128::new(20),
+                    user: "user".to_string(),
+                    receiver: "receiver".to_string(),
+                    unstake_index_list: vec![0, 1],
+                    transfer_deadline: 0,
+                },
+                pool_addr: POOL_ADDR.to_string(),
+                port_id: "icacontroller-stake_manager.ica".to_string(),
+                tx_type: TxType::UserWithdraw,
+            },
+        )?;
+
+        assert_eq!(rsp.messages.len(), 1);
+        assert_eq!(
+            rsp.messages[0].msg,
+            gen_unstake_nft_msg(
+                &Addr::unchecked("unstake_nft"),
+                &unstake_nft::msg::ExecuteMsg::Unlock {
+                    token_id: format!("{}/1", POOL_ADDR),
+                },
+            )?
+            .into()
+        );
+        for index in [0, 1] {
+            let unstake_info =
+                unstakes_of_index().load(&deps.storage, (POOL_ADDR.to_string(), index))?;
+            assert_eq!(unstake_info.status, WithdrawStatus::Default);
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,182 @@
+use crate::execute_withdraw::{
+    join_unstake_index_list, pending_withdraw_amount, settle_withdrawn_unstakes,
+    sudo_withdraw_failed_callback,
+};
+use crate::helper::{
+    check_ibc_fee, check_ica_channel_open, gen_msg_send, CAL_BASE, MAX_WITHDRAW_FOR_USERS,
+};
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
+use crate::state::{SudoPayload, TxType, INFO_OF_ICA_ID, POOLS, WITHDRAW_RECEIVERS};
+use crate::tx_callback::msg_with_sudo_callback;
+use crate::{error_conversion::ContractError, helper::tx_timeout_seconds};
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response, Uint128};
+use neutron_sdk::{
+    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    NeutronResult,
+};
+use std::ops::{Add, Div, Mul, Sub};
+
+pub fn execute_set_withdraw_receiver(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    pool_addr: String,
+    receiver: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    if receiver.is_empty() {
+        return Err(ContractError::WithdrawReceiverNotSet(info.sender.to_string()).into());
+    }
+    POOLS.load(deps.storage, pool_addr.clone())?;
+
+    WITHDRAW_RECEIVERS.save(
+        deps.storage,
+        (info.sender.clone(), pool_addr.clone()),
+        &receiver,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_withdraw_receiver")
+        .add_attribute("pool", pool_addr)
+        .add_attribute("user", info.sender)
+        .add_attribute("receiver", receiver))
+}
+
+// any keeper may withdraw on behalf of users, each user is paid to the stored receiver
+pub fn execute_withdraw_for(
+    mut deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    pool_addr: String,
+    users: Vec<(Addr, Vec<u64>)>,
+    tip_receiver: Option<String>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    if users.is_empty() {
+        return Err(ContractError::EmptyUnstakeList {}.into());
+    }
+    if users.len() > MAX_WITHDRAW_FOR_USERS {
+        return Err(ContractError::WithdrawForUsersOverLimit {}.into());
+    }
+
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+
+    let mut total_withdraw_amount = Uint128::zero();
+    let mut total_tip = Uint128::zero();
+    let mut unstake_index_list = vec![];
+    let mut send_msgs = vec![];
+    let mut lock_msgs = vec![];
+    for (user, user_index_list) in users {
+        if user_index_list.is_empty() {
+            return Err(ContractError::EmptyUnstakeList {}.into());
+        }
+        let receiver = WITHDRAW_RECEIVERS
+            .may_load(deps.storage, (user.clone(), pool_addr.clone()))?
+            .ok_or_else(|| ContractError::WithdrawReceiverNotSet(user.to_string()))?;
+
+        let (user_amount, user_lock_msgs) = pending_withdraw_amount(
+            deps.branch(),
+            &pool_info,
+            pool_addr.clone(),
+            &user,
+            &user_index_list,
+        )?;
+        let tip = if tip_receiver.is_some() {
+            user_amount.mul(pool_info.withdraw_for_tip).div(CAL_BASE)
+        } else {
+            Uint128::zero()
+        };
+        let receive_amount = user_amount.sub(tip);
+        if receive_amount.is_zero() {
+            return Err(ContractError::EncodeErrZeroWithdrawAmount {}.into());
+        }
+
+        send_msgs.push(gen_msg_send(
+            pool_addr.clone(),
+            receiver,
+            pool_info.remote_denom.clone(),
+            receive_amount.to_string(),
+        )?);
+        total_withdraw_amount = total_withdraw_amount.add(user_amount);
+        total_tip = total_tip.add(tip);
+        unstake_index_list.extend(user_index_list);
+        lock_msgs.extend(user_lock_msgs);
+    }
+
+    if let Some(tip_receiver) = tip_receiver {
+        if !total_tip.is_zero() {
+            send_msgs.push(gen_msg_send(
+                pool_addr.clone(),
+                tip_receiver,
+                pool_info.remote_denom.clone(),
+                total_tip.to_string(),
+            )?);
+        }
+    }
+
+    // the keeper pays the ibc fee
+    let ibc_fee = check_ibc_fee(deps.as_ref(), &info)?;
+    let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    check_ica_channel_open(&pool_ica_info)?;
+    let cosmos_msg = NeutronMsg::submit_tx(
+        pool_ica_info.ctrl_connection_id.clone(),
+        pool_info.ica_id.clone(),
+        send_msgs,
+        "".to_string(),
+        tx_timeout_seconds(&pool_info, &TxType::WithdrawFor),
+        ibc_fee,
+    );
+
+    let submsg = msg_with_sudo_callback(
+        deps.branch(),
+        cosmos_msg,
+        SudoPayload {
+            port_id: pool_ica_info.ctrl_port_id,
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::WithdrawFor {
+                amount: total_withdraw_amount,
+                keeper: info.sender.to_string(),
+                tip: total_tip,
+                unstake_index_list,
+            },
+            pool_addr: pool_addr.clone(),
+            tx_type: TxType::WithdrawFor,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_messages(lock_msgs)
+        .add_submessage(submsg))
+}
+
+pub fn sudo_withdraw_for_callback(
+    deps: DepsMut,
+    payload: SudoPayload,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let SudoData::WithdrawFor {
+        amount,
+        keeper,
+        tip,
+        unstake_index_list,
+    } = payload.data
+    else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
+    let burn_msgs =
+        settle_withdrawn_unstakes(deps, payload.pool_addr.clone(), &unstake_index_list)?;
+
+    Ok(Response::new()
+        .add_messages(burn_msgs)
+        .add_attribute("action", "withdraw_for")
+        .add_attribute("keeper", keeper)
+        .add_attribute("pool", payload.pool_addr.clone())
+        .add_attribute("tip", tip)
+        .add_attribute(
+            "unstake_index_list",
+            join_unstake_index_list(&unstake_index_list),
+        )
+        .add_attribute("amount", amount))
+}
+
+pub fn sudo_withdraw_for_failed_callback(
+    deps: DepsMut,
+    payload: SudoPayload,
+) -> NeutronResult<Response<NeutronMsg>> {
+    sudo_withdraw_failed_callback(deps, payload)
+}
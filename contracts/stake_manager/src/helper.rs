@@ -2,6 +2,7 @@ use crate::query_callback::register_query_submsg;
 use crate::state::{
     IcaInfo, PoolInfo, QueryKind, SudoPayload, TxType, ERA_RATE, POOLS, TOTAL_STACK_FEE,
 };
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
 use crate::state::{ADDRESS_TO_REPLY_ID, INFO_OF_ICA_ID, REPLY_ID_TO_QUERY_ID};
 use crate::tx_callback::msg_with_sudo_callback;
 use crate::{error_conversion::ContractError, state::EraStatus};
@@ -9,16 +10,20 @@ use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
 use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgSetWithdrawAddress;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::{MsgBeginRedelegate, MsgDelegate};
+use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer;
 use cosmos_sdk_proto::prost::Message;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{instantiate2_address, to_json_binary, SubMsg, Uint64, WasmMsg};
+use cosmwasm_std::{instantiate2_address, to_json_binary, Addr, SubMsg, Uint64, WasmMsg};
 use cosmwasm_std::{Binary, Deps, DepsMut, QueryRequest, StdResult, Uint128};
 use cosmwasm_std::{Env, MessageInfo, Response};
 use lsd_token::msg::InstantiateMinterData;
 use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
 use neutron_sdk::bindings::query::NeutronQuery;
-use neutron_sdk::bindings::types::ProtobufAny;
+use neutron_sdk::bindings::types::{KVKey, ProtobufAny};
+use neutron_sdk::interchain_queries::helpers::decode_and_convert;
+use neutron_sdk::interchain_queries::v045::helpers::create_account_denom_balance_key;
 use neutron_sdk::interchain_queries::v045::new_register_delegator_delegations_query_msg as v045_new_register_delegator_delegations_query_msg;
+use neutron_sdk::interchain_queries::v045::types::BANK_STORE_KEY;
 use neutron_sdk::interchain_queries::v045::{
     new_register_balance_query_msg, new_register_staking_validators_query_msg,
 };
@@ -33,6 +38,7 @@ pub const FEE_DENOM: &str = "untrn";
 pub const ICA_WITHDRAW_SUFIX: &str = "-withdraw_addr";
 pub const INTERCHAIN_ACCOUNT_ID_LEN_LIMIT: usize = 10;
 pub const CAL_BASE: Uint128 = Uint128::new(1_000_000);
+pub const YEAR_SECONDS: u64 = 365 * 24 * 60 * 60;
 pub const DEFAULT_RATE: Uint128 = Uint128::new(1_000_000);
 pub const DEFAULT_DECIMALS: u8 = 6;
 pub const DEFAULT_ERA_SECONDS: u64 = 86400; //24h
@@ -40,9 +46,27 @@ pub const MIN_ERA_SECONDS: u64 = 28800; //8h
 pub const MAX_ERA_SECONDS: u64 = 86400; //24h
 
 pub const STAKE_SPLIT_THRESHOLD: Uint128 = Uint128::new(10_000);
+// default of the host chain MaxEntries of unbonding and redelegation entries per pair
+pub const DEFAULT_MAX_ENTRIES: u64 = 7;
+// per validator loss below this is treated as share rounding rather than a slash
+pub const SLASH_DUST: Uint128 = Uint128::new(1000);
+// validator weights and rebalance tolerance are in basis points
+pub const WEIGHT_BASE: u64 = 10_000;
+
+// keeper tip of WithdrawFor, in CAL_BASE, at most 1%
+pub const MAX_WITHDRAW_FOR_TIP: Uint128 = Uint128::new(10_000);
+pub const MAX_WITHDRAW_FOR_USERS: usize = 10;
+pub const MAX_EXTRA_REWARD_DENOMS: usize = 10;
+pub const MAX_UNBOND_COMMISSION_TIERS: usize = 10;
+
+pub const DEFAULT_QUERY_LIMIT: u32 = 10;
+pub const MAX_QUERY_LIMIT: u32 = 30;
 
 // Default timeout for SubmitTX is 30h
 pub const DEFAULT_TIMEOUT_SECONDS: u64 = 30 * 60 * 60;
+// bounds of the per pool tx timeouts, 10min to 7d
+pub const MIN_TX_TIMEOUT_SECONDS: u64 = 10 * 60;
+pub const MAX_TX_TIMEOUT_SECONDS: u64 = 7 * 24 * 60 * 60;
 pub const DEFAULT_UPDATE_PERIOD: u64 = 86400;
 pub const DEFAULT_FAST_PERIOD: u64 = 60;
 
@@ -172,6 +196,35 @@ pub fn get_withdraw_ica_id(interchain_account_id: String) -> String {
     format!("{}{}", interchain_account_id.clone(), ICA_WITHDRAW_SUFIX)
 }
 
+// "icacontroller-{contract}.{ica_id}" => (pool ica id, is withdraw ica)
+pub fn parse_ica_port_id(port_id: &str) -> Option<(String, bool)> {
+    let (_, ica_id_raw) = port_id.split_once('.')?;
+    if ica_id_raw.contains('.') {
+        return None;
+    }
+    match ica_id_raw.strip_suffix(ICA_WITHDRAW_SUFIX) {
+        Some(ica_id) => Some((ica_id.to_string(), true)),
+        None => Some((ica_id_raw.to_string(), false)),
+    }
+}
+
+// timeout of the ica or transfer packet of a tx type, the pool may override the default
+pub fn tx_timeout_seconds(pool_info: &PoolInfo, tx_type: &TxType) -> u64 {
+    pool_info
+        .tx_timeouts
+        .iter()
+        .find(|tx_timeout| tx_timeout.tx_type == *tx_type)
+        .map(|tx_timeout| tx_timeout.timeout_seconds)
+        .unwrap_or(DEFAULT_TIMEOUT_SECONDS)
+}
+
+pub fn check_ica_channel_open(ica_info: &IcaInfo) -> NeutronResult<()> {
+    if ica_info.closed {
+        return Err(ContractError::IcaChannelClosed(ica_info.ctrl_channel_id.clone()).into());
+    }
+    Ok(())
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct RawCoin {
     #[prost(string, tag = "1")]
@@ -218,6 +271,53 @@ pub fn redeem_token_for_share_msg(
     build_msg(delegator.into(), token.into())
 }
 
+pub fn gen_msg_execute_contract(
+    sender: String,
+    contract: String,
+    msg: Binary,
+    funds: Vec<cosmwasm_std::Coin>,
+) -> ProtobufAny {
+    #[derive(Clone, PartialEq, Message)]
+    struct MsgExecuteContract {
+        #[prost(string, tag = "1")]
+        sender: String,
+        #[prost(string, tag = "2")]
+        contract: String,
+        #[prost(bytes = "vec", tag = "3")]
+        msg: Vec<u8>,
+        #[prost(message, repeated, tag = "5")]
+        funds: Vec<RawCoin>,
+    }
+
+    let msg = MsgExecuteContract {
+        sender,
+        contract,
+        msg: msg.to_vec(),
+        funds: funds.into_iter().map(RawCoin::from).collect(),
+    };
+
+    ProtobufAny {
+        type_url: "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+        value: msg.encode_to_vec().into(),
+    }
+}
+
+// keys of the withdraw ica balances icq, remote_denom and the extra reward denoms
+pub fn withdraw_balances_kv_keys(
+    withdraw_addr: &str,
+    pool_info: &PoolInfo,
+) -> NeutronResult<Vec<KVKey>> {
+    let addr_bytes = decode_and_convert(withdraw_addr)?;
+    let mut keys = vec![];
+    for denom in std::iter::once(&pool_info.remote_denom).chain(&pool_info.extra_reward_denoms) {
+        keys.push(KVKey {
+            path: BANK_STORE_KEY.to_string(),
+            key: Binary(create_account_denom_balance_key(&addr_bytes, denom)?),
+        });
+    }
+    Ok(keys)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DenomTrace {
@@ -489,7 +589,7 @@ pub fn set_withdraw_sub_msg(
             value: Binary::from(buf),
         }],
         "".to_string(),
-        DEFAULT_TIMEOUT_SECONDS,
+        tx_timeout_seconds(&pool_info, &TxType::SetWithdrawAddr),
         fee.clone(),
     );
 
@@ -500,7 +600,10 @@ pub fn set_withdraw_sub_msg(
         cosmos_msg,
         SudoPayload {
             port_id: pool_ica_info.ctrl_port_id,
-            message: withdraw_ica_info.ica_addr,
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::SetWithdrawAddr {
+                withdraw_addr: withdraw_ica_info.ica_addr,
+            },
             pool_addr: pool_ica_info.ica_addr.clone(),
             tx_type: TxType::SetWithdrawAddr,
         },
@@ -643,3 +746,62 @@ pub fn gen_msg_send(
         value: Binary::from(buf),
     })
 }
+
+// unstake nft token ids carry the pool, so pools may share the nft contract
+pub fn unstake_nft_token_id(pool_addr: &str, unstake_index: u64) -> String {
+    format!("{}/{}", pool_addr, unstake_index)
+}
+
+pub fn gen_unstake_nft_msg(
+    unstake_nft: &Addr,
+    msg: &unstake_nft::msg::ExecuteMsg,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: unstake_nft.to_string(),
+        msg: to_json_binary(msg)?,
+        funds: vec![],
+    })
+}
+
+pub fn gen_msg_transfer(
+    source_channel: String,
+    sender: String,
+    receiver: String,
+    denom: String,
+    amount: String,
+    timeout_timestamp: u64,
+) -> NeutronResult<ProtobufAny> {
+    // interchain tx ibc transfer atom back to neutron
+    let ica_transfer = MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel,
+        token: Some(Coin { denom, amount }),
+        sender,
+        receiver,
+        timeout_height: None,
+        timeout_timestamp,
+    };
+    let mut buf = Vec::new();
+    buf.reserve(ica_transfer.encoded_len());
+
+    ica_transfer
+        .encode(&mut buf)
+        .map_err(|e| ContractError::EncodeError(e.to_string()))?;
+
+    Ok(ProtobufAny {
+        type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+        value: Binary::from(buf),
+    })
+}
+
+// unbond start timestamps whose unbonding entry is still immature at current_time
+pub fn immature_unbond_times(
+    timestamps: Vec<u64>,
+    current_time: u64,
+    unbonding_seconds: u64,
+) -> Vec<u64> {
+    timestamps
+        .into_iter()
+        .filter(|timestamp| timestamp + unbonding_seconds >= current_time)
+        .collect()
+}
@@ -9,27 +9,40 @@ pub mod execute_config_pool;
 pub mod execute_era_active;
 pub mod execute_era_bond;
 pub mod execute_era_collect_withdraw;
+pub mod execute_era_process;
 pub mod execute_era_rebond;
 pub mod execute_era_update;
 pub mod execute_icq_update_period;
 pub mod execute_init_pool;
+pub mod execute_instant_unstake;
 pub mod execute_migrate_pool;
 pub mod execute_pool_add_validator;
 pub mod execute_pool_delegate;
+pub mod execute_pool_rebalance;
 pub mod execute_pool_rm_validator;
 pub mod execute_pool_update_validator;
 pub mod execute_register_pool;
+pub mod execute_resolve_pending_tx;
 pub mod execute_stake;
 pub mod execute_stake_lsm;
 pub mod execute_unstake;
 pub mod execute_update_validators_icq;
 pub mod execute_withdraw;
+pub mod execute_withdraw_for;
 
 pub mod error_conversion;
+pub mod execute_admin_transfer;
 pub mod execute_config_stack;
 pub mod execute_open_channel;
 pub mod execute_redeem_token_for_share;
+pub mod execute_roles;
 pub mod helper;
+pub mod migrate_sudo_payload;
 pub mod query;
 pub mod query_callback;
+pub mod redelegation;
+pub mod slashing;
 pub mod tx_callback;
+pub mod unbond_commission;
+pub mod validator_health;
+pub mod validator_selection;
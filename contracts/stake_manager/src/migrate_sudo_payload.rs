@@ -0,0 +1,152 @@
+use crate::state::{
+    SudoData, SudoPayload, TxType, REPLY_ID_TO_PAYLOAD, SUDO_PAYLOAD, SUDO_PAYLOAD_VERSION,
+};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{from_json, to_json_vec, Binary, Order, StdError, StdResult, Storage, Uint128};
+use std::str::FromStr;
+
+// payload saved before SUDO_PAYLOAD_VERSION, with the callback data joined into message
+#[cw_serde]
+struct LegacySudoPayload {
+    message: String,
+    pool_addr: String,
+    port_id: String,
+    tx_type: TxType,
+}
+
+fn unsupported(message: &str) -> StdError {
+    StdError::generic_err(format!("unsupported legacy sudo payload: {}", message))
+}
+
+fn parse_index_list(message: &str, joined: &str) -> StdResult<Vec<u64>> {
+    joined
+        .split('_')
+        .map(|index| index.parse::<u64>().map_err(|_| unsupported(message)))
+        .collect()
+}
+
+fn legacy_sudo_data(tx_type: &TxType, message: &str) -> StdResult<SudoData> {
+    let data = match tx_type {
+        TxType::SetWithdrawAddr => SudoData::SetWithdrawAddr {
+            withdraw_addr: message.to_string(),
+        },
+        TxType::EraBond => SudoData::EraBond {
+            unbond_validators: if message.is_empty() {
+                vec![]
+            } else {
+                message.split('_').map(String::from).collect()
+            },
+        },
+        TxType::UpdateValidator => SudoData::UpdateValidator {
+            new_validators: message.split('_').map(String::from).collect(),
+            redelegating_pairs: vec![],
+        },
+        TxType::RmValidator => SudoData::RmValidator {
+            validator: message.to_string(),
+            redelegating_pairs: vec![],
+        },
+        TxType::RedeemTokenForShare => SudoData::RedeemTokenForShare {
+            denoms: message.split(',').map(String::from).collect(),
+        },
+        // "{staker}_{token_amount}_{share_token_amount}_{ibc_denom}_{denom}", only the denom may hold '_'
+        TxType::StakeLsm => {
+            let parts: Vec<&str> = message.splitn(5, '_').collect();
+            if parts.len() != 5 {
+                return Err(unsupported(message));
+            }
+            SudoData::StakeLsm {
+                staker: parts[0].to_string(),
+                token_amount: Uint128::from_str(parts[1])?,
+                share_token_amount: Uint128::from_str(parts[2])?,
+                share_token_ibc_denom: parts[3].to_string(),
+                share_token_denom: parts[4].to_string(),
+            }
+        }
+        // "{amount}_{user}_{receiver}_{index}_{index}..."
+        TxType::UserWithdraw | TxType::UserWithdrawToNeutron => {
+            let parts: Vec<&str> = message.splitn(4, '_').collect();
+            if parts.len() != 4 {
+                return Err(unsupported(message));
+            }
+            SudoData::UserWithdraw {
+                amount: Uint128::from_str(parts[0])?,
+                user: parts[1].to_string(),
+                receiver: parts[2].to_string(),
+                unstake_index_list: parse_index_list(message, parts[3])?,
+                transfer_deadline: 0,
+            }
+        }
+        // "{amount}_{keeper}_{tip}_{index}_{index}..."
+        TxType::WithdrawFor => {
+            let parts: Vec<&str> = message.splitn(4, '_').collect();
+            if parts.len() != 4 {
+                return Err(unsupported(message));
+            }
+            SudoData::WithdrawFor {
+                amount: Uint128::from_str(parts[0])?,
+                keeper: parts[1].to_string(),
+                tip: Uint128::from_str(parts[2])?,
+                unstake_index_list: parse_index_list(message, parts[3])?,
+            }
+        }
+        TxType::PoolRebalance | TxType::RedelegateInactive => SudoData::Redelegate {
+            redelegating_pairs: vec![],
+        },
+        TxType::EraUpdate | TxType::EraCollectWithdraw | TxType::EraRebond => SudoData::Empty {},
+    };
+
+    Ok(data)
+}
+
+// returns None if the stored bytes already hold a versioned payload
+fn upgrade_payload(data: &[u8]) -> StdResult<Option<SudoPayload>> {
+    if from_json::<SudoPayload>(Binary::from(data)).is_ok() {
+        return Ok(None);
+    }
+
+    let legacy: LegacySudoPayload = from_json(Binary::from(data))?;
+    Ok(Some(SudoPayload {
+        version: SUDO_PAYLOAD_VERSION,
+        data: legacy_sudo_data(&legacy.tx_type, &legacy.message)?,
+        pool_addr: legacy.pool_addr,
+        port_id: legacy.port_id,
+        tx_type: legacy.tx_type,
+    }))
+}
+
+// rewrite in-flight payloads into the versioned layout, returns (migrated, failed) counts.
+// failed entries are left untouched so their callbacks error instead of acting on bad data
+pub fn migrate_sudo_payloads(storage: &mut dyn Storage) -> StdResult<(u64, u64)> {
+    let mut migrated = 0;
+    let mut failed = 0;
+
+    let sudo_payloads = SUDO_PAYLOAD
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, data) in sudo_payloads {
+        match upgrade_payload(&data) {
+            Ok(Some(payload)) => {
+                SUDO_PAYLOAD.save(storage, key, &to_json_vec(&payload)?)?;
+                migrated += 1;
+            }
+            Ok(None) => {}
+            Err(_) => failed += 1,
+        }
+    }
+
+    let reply_payloads = REPLY_ID_TO_PAYLOAD
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, data) in reply_payloads {
+        match upgrade_payload(&data) {
+            Ok(Some(payload)) => {
+                REPLY_ID_TO_PAYLOAD.save(storage, id, &to_json_vec(&payload)?)?;
+                migrated += 1;
+            }
+            Ok(None) => {}
+            Err(_) => failed += 1,
+        }
+    }
+
+    Ok((migrated, failed))
+}
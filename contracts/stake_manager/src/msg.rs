@@ -1,9 +1,15 @@
 use crate::state::{
-    BalanceResponse, DelegatorDelegationsResponse, EraSnapshot, IcaInfo, IcaInfos, PoolInfo,
-    QueryIds, QueryKind, Stack, UnstakeInfo,
+    AprResponse, BalanceResponse, DelegatorDelegationsResponse, EraRateEntry, EraSnapshot,
+    ExtraRewardRoute, FeeMode, IcaInfo, IcaInfos, PendingAdmin, PendingTx, PendingTxOutcome,
+    PoolHealthResponse, PoolInfo, PoolSummary, QueryIds, QueryKind, RedelegationLimitsResponse,
+    Role, RoleMember, SimulateStakeResponse, SimulateUnstakeResponse, SimulateWithdrawResponse,
+    SlashEvent, SlashPolicy, Stack, TransferOutcome, TxTimeout, UnbondCommissionSchedule,
+    UnstakeInfo, ValidatorSelectStrategy, ValidatorStatusEvent, ValidatorUnbondSlotsResponse,
+    ValidatorWeight, WithdrawMode, WithdrawStatus,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_utils::Expiration;
 use neutron_sdk::{
     bindings::query::{QueryInterchainAccountAddressResponse, QueryRegisteredQueryResponse},
     interchain_queries::v045::queries::ValidatorResponse,
@@ -40,6 +46,16 @@ pub enum QueryMsg {
     PoolInfo { pool_addr: String },
     #[returns(Stack)]
     StackInfo {},
+    #[returns(Option<PendingAdmin>)]
+    PendingStackAdmin {},
+    #[returns(Option<PendingAdmin>)]
+    PendingPoolAdmin { pool_addr: String },
+    #[returns(Vec<RoleMember>)]
+    Roles {
+        pool_addr: String,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
     #[returns(Uint128)]
     TotalStackFee { pool_addr: String },
     #[returns(EraSnapshot)]
@@ -63,10 +79,79 @@ pub enum QueryMsg {
         pool_addr: String,
         user_neutron_addr: Addr,
     },
+    #[returns(Option<String>)]
+    WithdrawReceiver {
+        pool_addr: String,
+        user_neutron_addr: Addr,
+    },
     #[returns(Uint128)]
     EraRate { pool_addr: String, era: u64 },
+    #[returns(Vec<EraRateEntry>)]
+    EraRates {
+        pool_addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(AprResponse)]
+    Apr { pool_addr: String, window_eras: u64 },
     #[returns(QueryIds)]
     QueryIds { pool_addr: String },
+    #[returns(RedelegationLimitsResponse)]
+    RedelegationLimits { pool_addr: String },
+    #[returns(ValidatorUnbondSlotsResponse)]
+    ValidatorUnbondSlots { pool_addr: String },
+    #[returns([ValidatorStatusEvent])]
+    ValidatorStatusEvents {
+        pool_addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns([SlashEvent])]
+    SlashEvents {
+        pool_addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns([PendingTx])]
+    PendingTxs {
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+    #[returns(SimulateUnstakeResponse)]
+    SimulateUnstake {
+        pool_addr: String,
+        user_neutron_addr: Addr,
+        lsd_token_amount: Uint128,
+    },
+    // without status it lists all unstakes of the pool
+    #[returns(Vec<UnstakeInfo>)]
+    PoolUnstakes {
+        pool_addr: String,
+        status: Option<WithdrawStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(PoolHealthResponse)]
+    PoolHealth { pool_addr: String },
+    #[returns(Vec<PoolSummary>)]
+    Pools {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<UnstakeInfo>)]
+    WithdrawableUnstakes { pool_addr: String, user: Addr },
+    #[returns(SimulateStakeResponse)]
+    SimulateStake {
+        pool_addr: String,
+        token_amount: Uint128,
+    },
+    // without unstake_index_list it simulates all unstakes made by the user
+    #[returns(SimulateWithdrawResponse)]
+    SimulateWithdraw {
+        pool_addr: String,
+        user_neutron_addr: Addr,
+        unstake_index_list: Option<Vec<u64>>,
+    },
 }
 
 #[cw_serde]
@@ -121,9 +206,11 @@ pub struct ConfigStackParams {
     pub stack_fee_commission: Option<Uint128>,
     pub lsd_token_code_id: Option<u64>,
     pub add_entrusted_pool: Option<String>,
+    pub remove_entrusted_pool: Option<String>,
 }
 
 #[cw_serde]
+#[derive(Default)]
 pub struct ConfigPoolParams {
     pub pool_addr: String,
     pub platform_fee_receiver: Option<String>,
@@ -138,6 +225,27 @@ pub struct ConfigPoolParams {
     pub lsm_pending_limit: Option<u64>,
     pub rate_change_limit: Option<Uint128>,
     pub new_admin: Option<Addr>,
+    pub validator_select_strategy: Option<ValidatorSelectStrategy>,
+    pub validator_weights: Option<Vec<ValidatorWeight>>,
+    pub rebalance_tolerance: Option<u64>,
+    pub max_entries: Option<u64>,
+    pub slash_policy: Option<SlashPolicy>,
+    pub slash_unstake_haircut: Option<bool>,
+    pub auto_redelegate_inactive: Option<bool>,
+    pub liquidity_buffer_target: Option<Uint128>,
+    pub liquidity_buffer_ratio: Option<Uint128>,
+    pub instant_unstake_fee_min: Option<Uint128>,
+    pub instant_unstake_fee_max: Option<Uint128>,
+    pub unstake_nft: Option<Addr>,
+    pub withdraw_for_tip: Option<Uint128>,
+    pub host_transfer_channel_id: Option<String>,
+    // replaces all overrides, an empty list restores the default everywhere
+    pub tx_timeouts: Option<Vec<TxTimeout>>,
+    // replaces the tracked denoms and updates the withdraw ica balance icq
+    pub extra_reward_denoms: Option<Vec<String>>,
+    pub extra_reward_route: Option<ExtraRewardRoute>,
+    pub fee_mode: Option<FeeMode>,
+    pub unbond_commission_schedule: Option<UnbondCommissionSchedule>,
 }
 
 #[cw_serde]
@@ -154,6 +262,39 @@ pub enum ExecuteMsg {
         pool_addr: String,
         closed_channel_id: String,
     },
+    // the proposed admin takes over by accepting before expires,
+    // a pending proposal blocks ConfigStack/ConfigPool new_admin
+    ProposeStackAdmin {
+        new_admin: Addr,
+        expires: Option<Expiration>,
+    },
+    AcceptStackAdmin {},
+    CancelStackAdminTransfer {},
+    ProposePoolAdmin {
+        pool_addr: String,
+        new_admin: Addr,
+        expires: Option<Expiration>,
+    },
+    AcceptPoolAdmin {
+        pool_addr: String,
+    },
+    CancelPoolAdminTransfer {
+        pool_addr: String,
+    },
+    // granted and revoked by the pool admin
+    GrantRole {
+        pool_addr: String,
+        account: Addr,
+        role: Role,
+    },
+    RevokeRole {
+        pool_addr: String,
+        account: Addr,
+        role: Role,
+    },
+    ReopenChannel {
+        pool_addr: String,
+    },
     RedeemTokenForShare {
         pool_addr: String,
         tokens: Vec<Coin>,
@@ -166,10 +307,41 @@ pub enum ExecuteMsg {
         amount: Uint128,
         pool_addr: String,
     },
+    InstantUnstake {
+        amount: Uint128,
+        pool_addr: String,
+    },
     Withdraw {
         pool_addr: String,
         receiver: Addr,
         unstake_index_list: Vec<u64>,
+        mode: Option<WithdrawMode>,
+    },
+    ResolvePendingTx {
+        channel_id: String,
+        seq_id: u64,
+        outcome: PendingTxOutcome,
+        // required to resolve a tx submitted before its deadline was recorded
+        force: Option<bool>,
+    },
+    // pool admin only, fallback for a transfer to neutron whose result never reaches the contract.
+    // an error or timeout of the withdraw ica tx resets the unstakes in its sudo callback, but the
+    // ics-20 ack of the transfer sent by the host chain is delivered to the host chain, so
+    // Delivered and Refunded are taken from the admin as is: a wrong Delivered drops unstakes
+    // that were refunded to the pool, a wrong Refunded lets delivered unstakes be withdrawn again
+    ResolveNeutronWithdraw {
+        pool_addr: String,
+        unstake_index_list: Vec<u64>,
+        outcome: TransferOutcome,
+    },
+    SetWithdrawReceiver {
+        pool_addr: String,
+        receiver: String,
+    },
+    WithdrawFor {
+        pool_addr: String,
+        users: Vec<(Addr, Vec<u64>)>,
+        tip_receiver: Option<String>,
     },
     PoolRmValidator {
         pool_addr: String,
@@ -191,23 +363,30 @@ pub enum ExecuteMsg {
         pool_addr: String,
         stake_amount: Uint128,
     },
+    PoolRebalance {
+        pool_addr: String,
+    },
     EraUpdate {
         pool_addr: String,
     },
     EraBond {
         pool_addr: String,
-        select_vals: Vec<String>,
     },
     EraCollectWithdraw {
         pool_addr: String,
     },
     EraRebond {
         pool_addr: String,
-        select_vals: Vec<String>,
     },
     EraActive {
         pool_addr: String,
     },
+    AcknowledgeSlash {
+        pool_addr: String,
+    },
+    EraProcess {
+        pool_addr: String,
+    },
     StakeLsm {
         neutron_address: String,
         pool_addr: String,
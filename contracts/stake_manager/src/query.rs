@@ -1,10 +1,33 @@
+use crate::error_conversion::ContractError;
+use crate::execute_admin_transfer::{pending_pool_admin, pending_stack_admin};
+use crate::execute_stake::simulate_stake;
+use crate::execute_unstake::simulate_unstake;
+use crate::execute_withdraw::{check_unstake_withdrawable, simulate_withdraw};
+use crate::helper::{
+    immature_unbond_times, CAL_BASE, DEFAULT_QUERY_LIMIT, MAX_QUERY_LIMIT, YEAR_SECONDS,
+};
+use crate::redelegation::load_redelegation_limits;
+use crate::state::WITHDRAW_RECEIVERS;
+use crate::state::{
+    read_sudo_payload, PendingTx, SUDO_PAYLOAD, SUDO_PAYLOAD_DEADLINE, SUDO_PAYLOAD_TIME,
+};
+use crate::state::{unstakes_of_index, POOLS, REPLY_ID_TO_QUERY_ID, UNSTAKES_INDEX_FOR_USER};
+use crate::state::{AprResponse, EraRateEntry};
 use crate::state::{
     BalanceResponse, Balances, DelegatorDelegationsResponse, ADDRESS_TO_REPLY_ID, STACK,
     TOTAL_STACK_FEE,
 };
+use crate::state::{
+    EraStatus, IcqHealth, PoolHealthResponse, PoolNextAction, PoolSummary, UnstakeInfo,
+    ValidatorUpdateStatus, WithdrawStatus,
+};
 use crate::state::{IcaInfos, QueryIds, QueryKind, ERA_RATE, INFO_OF_ICA_ID};
-use crate::state::{POOLS, REPLY_ID_TO_QUERY_ID, UNSTAKES_INDEX_FOR_USER, UNSTAKES_OF_INDEX};
-use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Env};
+use crate::state::{RoleMember, POOL_ROLES};
+use crate::state::{SlashEvent, SLASH_EVENTS};
+use crate::state::{ValidatorStatusEvent, VALIDATOR_STATUS_EVENTS};
+use crate::state::{ValidatorUnbondSlot, ValidatorUnbondSlotsResponse, VALIDATORS_UNBONDS_TIME};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Env, Order, StdResult, Uint128};
+use cw_storage_plus::Bound;
 use neutron_sdk::{
     bindings::query::QueryRegisteredQueryResponse,
     interchain_queries::v045::queries::ValidatorResponse,
@@ -19,6 +42,7 @@ use neutron_sdk::{
     },
     NeutronResult,
 };
+use std::ops::{Div, Mul, Sub};
 use std::vec;
 
 pub fn query_user_unstake(
@@ -33,7 +57,7 @@ pub fn query_user_unstake(
     {
         for unstake_index in unstakes {
             let unstake_info =
-                UNSTAKES_OF_INDEX.load(deps.storage, (pool_addr.clone(), unstake_index))?;
+                unstakes_of_index().load(deps.storage, (pool_addr.clone(), unstake_index))?;
             results.push(unstake_info);
         }
     }
@@ -41,6 +65,263 @@ pub fn query_user_unstake(
     Ok(to_json_binary(&results)?)
 }
 
+pub fn query_pool_health(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    pool_addr: String,
+) -> NeutronResult<Binary> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    let (pool_ica_info, withdraw_ica_info, _) =
+        INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    let last_step_height = pool_info.era_snapshot.last_step_height;
+
+    let current_era = if pool_info.era_seconds == 0 {
+        pool_info.era
+    } else {
+        (env.block.time.seconds() / pool_info.era_seconds).saturating_add_signed(pool_info.offset)
+    };
+    let delegations_icq = icq_health(
+        deps,
+        pool_addr.clone(),
+        QueryKind::Delegations,
+        last_step_height,
+    )?;
+    let withdraw_balances_icq = icq_health(
+        deps,
+        withdraw_ica_info.ica_addr.clone(),
+        QueryKind::Balances,
+        last_step_height,
+    )?;
+    let closed_channel_ids = [&pool_ica_info, &withdraw_ica_info]
+        .into_iter()
+        .filter(|ica_info| ica_info.closed)
+        .map(|ica_info| ica_info.ctrl_channel_id.clone())
+        .collect::<Vec<String>>();
+
+    let mut pending_txs = vec![];
+    for key in SUDO_PAYLOAD.keys(deps.storage, None, None, Order::Ascending) {
+        let (channel_id, seq_id) = key?;
+        let payload = read_sudo_payload(deps.storage, channel_id.clone(), seq_id)?;
+        if payload.pool_addr != pool_addr {
+            continue;
+        }
+        let submitted_at =
+            SUDO_PAYLOAD_TIME.may_load(deps.storage, (channel_id.clone(), seq_id))?;
+        let deadline =
+            SUDO_PAYLOAD_DEADLINE.may_load(deps.storage, (channel_id.clone(), seq_id))?;
+        pending_txs.push(PendingTx {
+            payload,
+            channel_id,
+            seq_id,
+            submitted_at,
+            age: submitted_at.map(|time| env.block.time.seconds().saturating_sub(time)),
+            deadline,
+        });
+    }
+
+    // mirrors the checks of the era steps
+    let next_action = match pool_info.status {
+        EraStatus::RegisterEnded | EraStatus::InitFailed => PoolNextAction::InitPool,
+        _ if !closed_channel_ids.is_empty() => PoolNextAction::ReopenChannel,
+        EraStatus::InitStarted
+        | EraStatus::EraUpdateStarted
+        | EraStatus::BondStarted
+        | EraStatus::WithdrawStarted
+        | EraStatus::RebondStarted => PoolNextAction::WaitForTx,
+        EraStatus::ActiveEnded => {
+            if pool_info.validator_update_status != ValidatorUpdateStatus::End {
+                PoolNextAction::WaitForValidatorUpdate
+            } else if pool_info.paused {
+                PoolNextAction::Unpause
+            } else if current_era <= pool_info.era {
+                PoolNextAction::WaitForEra {
+                    era: pool_info.era + 1,
+                }
+            } else {
+                PoolNextAction::EraUpdate
+            }
+        }
+        EraStatus::EraUpdateEnded => {
+            if !delegations_icq.fresh {
+                PoolNextAction::WaitForIcq {
+                    query_kind: QueryKind::Delegations,
+                }
+            } else {
+                PoolNextAction::EraBond
+            }
+        }
+        EraStatus::BondEnded => {
+            // collect withdraw goes on without a balance result
+            if withdraw_balances_icq.last_submitted_local_height.is_some()
+                && !withdraw_balances_icq.fresh
+            {
+                PoolNextAction::WaitForIcq {
+                    query_kind: QueryKind::Balances,
+                }
+            } else {
+                PoolNextAction::EraCollectWithdraw
+            }
+        }
+        EraStatus::WithdrawEnded => {
+            if !pool_info.era_snapshot.restake_amount.is_zero() && !delegations_icq.fresh {
+                PoolNextAction::WaitForIcq {
+                    query_kind: QueryKind::Delegations,
+                }
+            } else {
+                PoolNextAction::EraRebond
+            }
+        }
+        EraStatus::RebondEnded => {
+            if !pool_info.share_tokens.is_empty() {
+                PoolNextAction::RedeemTokenForShare
+            } else if !delegations_icq.fresh {
+                PoolNextAction::WaitForIcq {
+                    query_kind: QueryKind::Delegations,
+                }
+            } else {
+                PoolNextAction::EraActive
+            }
+        }
+    };
+
+    Ok(to_json_binary(&PoolHealthResponse {
+        status: pool_info.status,
+        validator_update_status: pool_info.validator_update_status,
+        paused: pool_info.paused,
+        era: pool_info.era,
+        current_era,
+        last_step_height,
+        delegations_icq,
+        withdraw_balances_icq,
+        closed_channel_ids,
+        share_tokens: pool_info.share_tokens,
+        redeemming_share_token_denom: pool_info.redeemming_share_token_denom,
+        pending_txs,
+        next_action,
+    })?)
+}
+
+fn icq_health(
+    deps: Deps<NeutronQuery>,
+    addr: String,
+    query_kind: QueryKind,
+    last_step_height: u64,
+) -> NeutronResult<IcqHealth> {
+    let last_submitted_local_height = match ADDRESS_TO_REPLY_ID
+        .may_load(deps.storage, (addr.clone(), query_kind.clone().to_string()))?
+        .map(|contract_query_id| REPLY_ID_TO_QUERY_ID.may_load(deps.storage, contract_query_id))
+        .transpose()?
+        .flatten()
+    {
+        Some(registered_query_id) => Some(
+            get_registered_query(deps, registered_query_id)?
+                .registered_query
+                .last_submitted_result_local_height,
+        ),
+        None => None,
+    };
+
+    Ok(IcqHealth {
+        query_kind,
+        addr,
+        fresh: last_submitted_local_height.map_or(false, |height| height > last_step_height),
+        last_submitted_local_height,
+    })
+}
+
+pub fn query_pools(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> NeutronResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let stack = STACK.load(deps.storage)?;
+
+    let pools = POOLS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(pool_addr, pool_info)| PoolSummary {
+                entrusted: stack.pools.contains(&pool_addr),
+                pool_addr,
+                status: pool_info.status,
+                era: pool_info.era,
+                rate: pool_info.rate,
+                active: pool_info.active,
+                bond: pool_info.bond,
+                unbond: pool_info.unbond,
+                lsd_token: pool_info.lsd_token,
+                paused: pool_info.paused,
+            })
+        })
+        .collect::<StdResult<Vec<PoolSummary>>>()?;
+
+    Ok(to_json_binary(&pools)?)
+}
+
+pub fn query_pool_unstakes(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    status: Option<WithdrawStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> NeutronResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let unstakes = match status {
+        Some(status) => {
+            let start = start_after.map(|index| Bound::exclusive((pool_addr.clone(), index)));
+            unstakes_of_index()
+                .idx
+                .status
+                .prefix((pool_addr, status.index_key()))
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(_, unstake_info)| unstake_info))
+                .collect::<StdResult<Vec<UnstakeInfo>>>()?
+        }
+        None => unstakes_of_index()
+            .prefix(pool_addr)
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(_, unstake_info)| unstake_info))
+            .collect::<StdResult<Vec<UnstakeInfo>>>()?,
+    };
+
+    Ok(to_json_binary(&unstakes)?)
+}
+
+// unstakes made by the user that it can withdraw now, a claim nft moved away is skipped
+pub fn query_withdrawable_unstakes(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    user: Addr,
+) -> NeutronResult<Binary> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+
+    let unstakes = unstakes_of_index()
+        .idx
+        .unstaker
+        .prefix((pool_addr.clone(), user.to_string()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, unstake_info)| unstake_info))
+        .collect::<StdResult<Vec<UnstakeInfo>>>()?
+        .into_iter()
+        .filter(|unstake_info| {
+            check_unstake_withdrawable(&deps.querier, &pool_info, &pool_addr, &user, unstake_info)
+                .is_ok()
+        })
+        .collect::<Vec<UnstakeInfo>>();
+
+    Ok(to_json_binary(&unstakes)?)
+}
+
 pub fn query_user_unstake_index(
     deps: Deps<NeutronQuery>,
     pool_addr: String,
@@ -51,6 +332,127 @@ pub fn query_user_unstake_index(
     )?)
 }
 
+pub fn query_withdraw_receiver(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    user_neutron_addr: Addr,
+) -> NeutronResult<Binary> {
+    Ok(to_json_binary(
+        &WITHDRAW_RECEIVERS.may_load(deps.storage, (user_neutron_addr, pool_addr))?,
+    )?)
+}
+
+pub fn query_simulate_unstake(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    pool_addr: String,
+    user_neutron_addr: Addr,
+    lsd_token_amount: Uint128,
+) -> NeutronResult<Binary> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    Ok(to_json_binary(&simulate_unstake(
+        deps.storage,
+        &pool_info,
+        pool_addr,
+        user_neutron_addr,
+        lsd_token_amount,
+        env.block.time.seconds(),
+    )?)?)
+}
+
+pub fn query_era_rates(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> NeutronResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let rates = ERA_RATE
+        .prefix(pool_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(era, rate)| EraRateEntry { era, rate }))
+        .collect::<StdResult<Vec<EraRateEntry>>>()?;
+
+    Ok(to_json_binary(&rates)?)
+}
+
+// annualizes the rate growth over the last window_eras eras
+pub fn query_apr(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    window_eras: u64,
+) -> NeutronResult<Binary> {
+    if window_eras == 0 {
+        return Err(ContractError::AprWindowErasZero {}.into());
+    }
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    let end_era = pool_info.era;
+    let end_rate = pool_info.rate;
+
+    // eras before a migration have no rate, start from the first one recorded in the window
+    let (start_era, start_rate) = ERA_RATE
+        .prefix(pool_addr)
+        .range(
+            deps.storage,
+            Some(Bound::inclusive(end_era.saturating_sub(window_eras))),
+            None,
+            Order::Ascending,
+        )
+        .next()
+        .transpose()?
+        .unwrap_or((end_era, end_rate));
+
+    let elapsed_seconds =
+        Uint128::from(end_era.saturating_sub(start_era)).mul(Uint128::from(pool_info.era_seconds));
+    let apr = if elapsed_seconds.is_zero() || start_rate.is_zero() || end_rate <= start_rate {
+        Uint128::zero()
+    } else {
+        end_rate
+            .sub(start_rate)
+            .mul(CAL_BASE)
+            .mul(Uint128::from(YEAR_SECONDS))
+            .div(start_rate)
+            .div(elapsed_seconds)
+    };
+
+    Ok(to_json_binary(&AprResponse {
+        start_era,
+        start_rate,
+        end_era,
+        end_rate,
+        apr,
+    })?)
+}
+
+pub fn query_simulate_stake(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    token_amount: Uint128,
+) -> NeutronResult<Binary> {
+    let pool_info = POOLS.load(deps.storage, pool_addr)?;
+    Ok(to_json_binary(&simulate_stake(&pool_info, token_amount)?)?)
+}
+
+pub fn query_simulate_withdraw(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    user_neutron_addr: Addr,
+    unstake_index_list: Option<Vec<u64>>,
+) -> NeutronResult<Binary> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    Ok(to_json_binary(&simulate_withdraw(
+        &deps.querier,
+        deps.storage,
+        &pool_info,
+        pool_addr,
+        &user_neutron_addr,
+        unstake_index_list,
+    )?)?)
+}
+
 pub fn query_era_rate(
     deps: Deps<NeutronQuery>,
     pool_addr: String,
@@ -61,6 +463,147 @@ pub fn query_era_rate(
     )?)
 }
 
+pub fn query_redelegation_limits(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    pool_addr: String,
+) -> NeutronResult<Binary> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+
+    Ok(to_json_binary(&load_redelegation_limits(
+        deps.storage,
+        &pool_info,
+        pool_addr,
+        env.block.time.seconds(),
+    )?)?)
+}
+
+pub fn query_validator_unbond_slots(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    pool_addr: String,
+) -> NeutronResult<Binary> {
+    let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
+    let current_time = env.block.time.seconds();
+    let unbonding_seconds = pool_info.unbonding_period * pool_info.era_seconds;
+
+    // pool validators first, then validators out of the pool which still have entries
+    let mut validators = pool_info.validator_addrs.clone();
+    for item in VALIDATORS_UNBONDS_TIME.prefix(pool_addr.clone()).keys(
+        deps.storage,
+        None,
+        None,
+        Order::Ascending,
+    ) {
+        let validator = item?;
+        if !validators.contains(&validator) {
+            validators.push(validator);
+        }
+    }
+
+    let mut slots = vec![];
+    for validator in validators {
+        let timestamps = immature_unbond_times(
+            VALIDATORS_UNBONDS_TIME
+                .may_load(deps.storage, (pool_addr.clone(), validator.clone()))?
+                .unwrap_or_default(),
+            current_time,
+            unbonding_seconds,
+        );
+        let used_entries = timestamps.len() as u64;
+
+        slots.push(ValidatorUnbondSlot {
+            validator,
+            used_entries,
+            free_slots: pool_info.max_entries.saturating_sub(used_entries),
+            next_free_time: timestamps
+                .iter()
+                .min()
+                .map(|timestamp| timestamp + unbonding_seconds),
+        });
+    }
+
+    Ok(to_json_binary(&ValidatorUnbondSlotsResponse {
+        max_entries: pool_info.max_entries,
+        unbonding_seconds,
+        validators: slots,
+    })?)
+}
+
+pub fn query_slash_events(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> NeutronResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let events = SLASH_EVENTS
+        .prefix(pool_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, event)| event))
+        .collect::<StdResult<Vec<SlashEvent>>>()?;
+
+    Ok(to_json_binary(&events)?)
+}
+
+pub fn query_pending_txs(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> NeutronResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let keys = SUDO_PAYLOAD
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<(String, u64)>>>()?;
+
+    let mut pending_txs = vec![];
+    for (channel_id, seq_id) in keys {
+        let submitted_at =
+            SUDO_PAYLOAD_TIME.may_load(deps.storage, (channel_id.clone(), seq_id))?;
+        let deadline =
+            SUDO_PAYLOAD_DEADLINE.may_load(deps.storage, (channel_id.clone(), seq_id))?;
+        pending_txs.push(PendingTx {
+            payload: read_sudo_payload(deps.storage, channel_id.clone(), seq_id)?,
+            channel_id,
+            seq_id,
+            submitted_at,
+            age: submitted_at.map(|time| env.block.time.seconds().saturating_sub(time)),
+            deadline,
+        });
+    }
+
+    Ok(to_json_binary(&pending_txs)?)
+}
+
+// events of the eras after start_after, limit counts eras
+pub fn query_validator_status_events(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> NeutronResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let era_events = VALIDATOR_STATUS_EVENTS
+        .prefix(pool_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, events)| events))
+        .collect::<StdResult<Vec<Vec<ValidatorStatusEvent>>>>()?;
+
+    Ok(to_json_binary(
+        &era_events.into_iter().flatten().collect::<Vec<_>>(),
+    )?)
+}
+
 pub fn query_ids(deps: Deps<NeutronQuery>, pool_addr: String) -> NeutronResult<Binary> {
     let pool_info = POOLS.load(deps.storage, pool_addr.clone())?;
     let (_, withdraw, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id)?;
@@ -213,6 +756,41 @@ pub fn query_pool_info(
     Ok(to_json_binary(&pool_info)?)
 }
 
+pub fn query_pending_stack_admin(deps: Deps<NeutronQuery>, env: Env) -> NeutronResult<Binary> {
+    Ok(to_json_binary(&pending_stack_admin(deps.storage, &env)?)?)
+}
+
+pub fn query_pending_pool_admin(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    pool_addr: String,
+) -> NeutronResult<Binary> {
+    Ok(to_json_binary(&pending_pool_admin(
+        deps.storage,
+        &env,
+        pool_addr,
+    )?)?)
+}
+
+pub fn query_roles(
+    deps: Deps<NeutronQuery>,
+    pool_addr: String,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> NeutronResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let members = POOL_ROLES
+        .prefix(pool_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(account, roles)| RoleMember { account, roles }))
+        .collect::<StdResult<Vec<RoleMember>>>()?;
+
+    Ok(to_json_binary(&members)?)
+}
+
 pub fn query_stack_info(deps: Deps<NeutronQuery>) -> NeutronResult<Binary> {
     let stack_info = STACK.load(deps.storage)?;
 
@@ -0,0 +1,188 @@
+use crate::state::{
+    LockedValidator, PendingRedelegation, PoolInfo, RedelegationLimit, RedelegationLimitsResponse,
+    VALIDATORS_REDELEGATIONS_TIME,
+};
+use cosmwasm_std::{Order, StdResult, Storage};
+
+pub fn redelegation_seconds(pool_info: &PoolInfo) -> u64 {
+    pool_info.unbonding_period * pool_info.era_seconds
+}
+
+// immature redelegations of the pool at current_time
+pub fn load_redelegation_limits(
+    storage: &dyn Storage,
+    pool_info: &PoolInfo,
+    pool_addr: String,
+    current_time: u64,
+) -> StdResult<RedelegationLimitsResponse> {
+    let max_entries = pool_info.max_entries;
+    let mut pending_redelegations = vec![];
+    let mut locked_validators: Vec<LockedValidator> = vec![];
+
+    for item in VALIDATORS_REDELEGATIONS_TIME.sub_prefix(pool_addr).range(
+        storage,
+        None,
+        None,
+        Order::Ascending,
+    ) {
+        let ((src_validator, dst_validator), timestamps) = item?;
+        let completion_times: Vec<u64> = timestamps
+            .into_iter()
+            .filter(|completion_time| *completion_time > current_time)
+            .collect();
+        let Some(last_completion_time) = completion_times.iter().max().cloned() else {
+            continue;
+        };
+
+        if let Some(locked) = locked_validators
+            .iter_mut()
+            .find(|l| l.validator == dst_validator)
+        {
+            locked.until = locked.until.max(last_completion_time);
+        } else {
+            locked_validators.push(LockedValidator {
+                validator: dst_validator.clone(),
+                until: last_completion_time,
+            });
+        }
+
+        pending_redelegations.push(PendingRedelegation {
+            src_validator,
+            dst_validator,
+            free_entries: max_entries.saturating_sub(completion_times.len() as u64),
+            completion_times,
+        });
+    }
+
+    Ok(RedelegationLimitsResponse {
+        max_entries,
+        redelegation_seconds: redelegation_seconds(pool_info),
+        pending_redelegations,
+        locked_validators,
+    })
+}
+
+// returns the host chain rule that a redelegation from src to dst would break
+pub fn check_redelegation(
+    limits: &RedelegationLimitsResponse,
+    src_validator: &str,
+    dst_validator: &str,
+) -> Option<RedelegationLimit> {
+    if let Some(locked) = limits
+        .locked_validators
+        .iter()
+        .find(|l| l.validator == src_validator)
+    {
+        return Some(RedelegationLimit::TransitiveRedelegation {
+            src_validator: src_validator.to_string(),
+            until: locked.until,
+        });
+    }
+
+    if let Some(pending) = limits
+        .pending_redelegations
+        .iter()
+        .find(|p| p.src_validator == src_validator && p.dst_validator == dst_validator)
+    {
+        if pending.free_entries == 0 {
+            return Some(RedelegationLimit::MaxEntries {
+                max_entries: limits.max_entries,
+                next_free_time: pending
+                    .completion_times
+                    .iter()
+                    .min()
+                    .cloned()
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    None
+}
+
+// records the pairs carried by the acked redelegate tx payload with their completion time
+// and prunes the mature ones
+pub fn settle_redelegating_pairs(
+    storage: &mut dyn Storage,
+    pool_info: &PoolInfo,
+    pool_addr: String,
+    pairs: Vec<(String, String)>,
+    current_time: u64,
+) -> StdResult<()> {
+    let completion_time = current_time + redelegation_seconds(pool_info);
+    for (src_validator, dst_validator) in pairs {
+        let key = (pool_addr.clone(), src_validator, dst_validator);
+        let mut timestamps = VALIDATORS_REDELEGATIONS_TIME
+            .may_load(storage, key.clone())?
+            .unwrap_or_default();
+        timestamps.retain(|timestamp| *timestamp > current_time);
+        timestamps.push(completion_time);
+
+        VALIDATORS_REDELEGATIONS_TIME.save(storage, key, &timestamps)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execute_pool_rebalance::{
+        sudo_pool_rebalance_callback, sudo_pool_rebalance_failed_callback,
+    };
+    use crate::state::{
+        tests::BASELINE_POOL_INFO, SudoData, SudoPayload, TxType, POOLS, SUDO_PAYLOAD_VERSION,
+    };
+    use cosmwasm_std::from_json;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use neutron_sdk::NeutronResult;
+
+    fn redelegate_payload(src_validator: &str, dst_validator: &str) -> SudoPayload {
+        SudoPayload {
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::Redelegate {
+                redelegating_pairs: vec![(src_validator.to_string(), dst_validator.to_string())],
+            },
+            pool_addr: "pool".to_string(),
+            port_id: "icacontroller-stake_manager.ica".to_string(),
+            tx_type: TxType::PoolRebalance,
+        }
+    }
+
+    fn pending_pairs(limits: &RedelegationLimitsResponse) -> Vec<(String, String)> {
+        limits
+            .pending_redelegations
+            .iter()
+            .map(|p| (p.src_validator.clone(), p.dst_validator.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_redelegations_settle_their_own_pairs() -> NeutronResult<()> {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        let pool_info: PoolInfo = from_json(BASELINE_POOL_INFO)?;
+        POOLS.save(deps.as_mut().storage, "pool".to_string(), &pool_info)?;
+
+        // both txs are in flight before either ack arrives
+        let first = redelegate_payload("val_a", "val_b");
+        let second = redelegate_payload("val_c", "val_d");
+        let third = redelegate_payload("val_e", "val_f");
+
+        sudo_pool_rebalance_callback(deps.as_mut(), env.clone(), second)?;
+        sudo_pool_rebalance_failed_callback(deps.as_mut(), third)?;
+        sudo_pool_rebalance_callback(deps.as_mut(), env, first)?;
+
+        let limits =
+            load_redelegation_limits(deps.as_ref().storage, &pool_info, "pool".to_string(), now)?;
+        assert_eq!(
+            pending_pairs(&limits),
+            vec![
+                ("val_a".to_string(), "val_b".to_string()),
+                ("val_c".to_string(), "val_d".to_string()),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use crate::execute_withdraw::unstake_withdraw_era;
+use crate::helper::SLASH_DUST;
+use crate::state::{
+    unstakes_of_index, PoolInfo, ValidatorSlash, WithdrawStatus, EXPECTED_DELEGATIONS,
+};
+use cosmwasm_std::{Delegation, Order, StdResult, Storage, Uint128};
+use std::ops::{Add, Sub};
+
+// expected delegations of the pool after the era bond txs, starting from the current ICQ result
+pub fn save_expected_delegations(
+    storage: &mut dyn Storage,
+    pool_addr: String,
+    delegations: &[Delegation],
+    delegate_infos: &[(String, Uint128)],
+    undelegate_infos: &[(String, Uint128)],
+) -> StdResult<()> {
+    let mut expected: Vec<(String, Uint128)> = delegations
+        .iter()
+        .map(|d| (d.validator.clone(), d.amount.amount))
+        .collect();
+    add_expected(&mut expected, delegate_infos);
+    for (validator, amount) in undelegate_infos {
+        if let Some(item) = expected.iter_mut().find(|(v, _)| v == validator) {
+            item.1 = item.1.saturating_sub(*amount);
+        }
+    }
+
+    EXPECTED_DELEGATIONS.save(storage, pool_addr, &expected)
+}
+
+// adds the rebond delegations to the expected delegations of the pool
+pub fn add_expected_delegations(
+    storage: &mut dyn Storage,
+    pool_addr: String,
+    delegate_infos: &[(String, Uint128)],
+) -> StdResult<()> {
+    let mut expected = EXPECTED_DELEGATIONS
+        .may_load(storage, pool_addr.clone())?
+        .unwrap_or_default();
+    add_expected(&mut expected, delegate_infos);
+
+    EXPECTED_DELEGATIONS.save(storage, pool_addr, &expected)
+}
+
+fn add_expected(expected: &mut Vec<(String, Uint128)>, delegate_infos: &[(String, Uint128)]) {
+    for (validator, amount) in delegate_infos {
+        if let Some(item) = expected.iter_mut().find(|(v, _)| v == validator) {
+            item.1 = item.1.add(*amount);
+        } else {
+            expected.push((validator.clone(), *amount));
+        }
+    }
+}
+
+// validators whose delegation is below the expected one by more than the rounding dust
+pub fn detect_validator_slashes(
+    storage: &dyn Storage,
+    pool_addr: String,
+    delegations: &[Delegation],
+) -> StdResult<Vec<ValidatorSlash>> {
+    let expected = EXPECTED_DELEGATIONS
+        .may_load(storage, pool_addr)?
+        .unwrap_or_default();
+
+    let mut slashes = vec![];
+    for (validator, expected_amount) in expected {
+        let actual = delegations
+            .iter()
+            .find(|d| d.validator == validator)
+            .map(|d| d.amount.amount)
+            .unwrap_or(Uint128::zero());
+        let loss = expected_amount.saturating_sub(actual);
+        if loss > SLASH_DUST {
+            slashes.push(ValidatorSlash {
+                validator,
+                expected: expected_amount,
+                actual,
+                loss,
+            });
+        }
+    }
+
+    Ok(slashes)
+}
+
+// cuts the unstakes still unbonding on the host chain by loss / (loss + total_delegated)
+pub fn apply_unstake_haircut(
+    storage: &mut dyn Storage,
+    pool_info: &PoolInfo,
+    pool_addr: String,
+    loss: Uint128,
+    total_delegated: Uint128,
+) -> StdResult<Uint128> {
+    let base = total_delegated.add(loss);
+    if loss.is_zero() || base.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let unbonding_unstakes = unstakes_of_index()
+        .prefix(pool_addr.clone())
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .filter(|(_, unstake_info)| {
+            unstake_info.status == WithdrawStatus::Default
+                && unstake_withdraw_era(pool_info, unstake_info.era) > pool_info.era
+        })
+        .collect::<Vec<_>>();
+
+    let mut total_haircut = Uint128::zero();
+    for (index, mut unstake_info) in unbonding_unstakes {
+        let haircut = unstake_info.amount.multiply_ratio(loss, base);
+        if haircut.is_zero() {
+            continue;
+        }
+        unstake_info.amount = unstake_info.amount.sub(haircut);
+        total_haircut = total_haircut.add(haircut);
+
+        unstakes_of_index().save(storage, (pool_addr.clone(), index), &unstake_info)?;
+    }
+
+    Ok(total_haircut)
+}
@@ -1,9 +1,13 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{from_json, to_json_vec, Addr, Binary, Coin, StdResult, Storage, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    from_json, to_json_vec, Addr, Binary, Coin, Order, StdError, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
 
 use crate::helper::{
-    QUERY_REPLY_ID_RANGE_END, QUERY_REPLY_ID_RANGE_START, REPLY_ID_RANGE_END, REPLY_ID_RANGE_START,
+    DEFAULT_MAX_ENTRIES, QUERY_REPLY_ID_RANGE_END, QUERY_REPLY_ID_RANGE_START, REPLY_ID_RANGE_END,
+    REPLY_ID_RANGE_START,
 };
 
 #[cw_serde]
@@ -16,6 +20,42 @@ pub struct Stack {
 }
 
 pub const STACK: Item<Stack> = Item::new("stack");
+
+#[cw_serde]
+pub enum Role {
+    // sets paused
+    Pauser,
+    // PoolAddValidator, PoolRmValidator and PoolUpdateValidator
+    ValidatorManager,
+    // commissions, fee mode and receivers
+    FeeManager,
+    // drives the era steps, which are permissionless while no operator is granted
+    EraOperator,
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Pauser => write!(f, "pauser"),
+            Role::ValidatorManager => write!(f, "validator_manager"),
+            Role::FeeManager => write!(f, "fee_manager"),
+            Role::EraOperator => write!(f, "era_operator"),
+        }
+    }
+}
+
+// (poolAddress,account) => roles granted by the pool admin
+pub const POOL_ROLES: Map<(String, Addr), Vec<Role>> = Map::new("pool_roles");
+
+#[cw_serde]
+pub struct PendingAdmin {
+    pub new_admin: Addr,
+    pub expires: Expiration,
+}
+
+pub const PENDING_STACK_ADMIN: Item<PendingAdmin> = Item::new("pending_stack_admin");
+// poolAddress => pending pool admin
+pub const PENDING_POOL_ADMINS: Map<String, PendingAdmin> = Map::new("pending_pool_admins");
 pub const TOTAL_STACK_FEE: Map<String, Uint128> = Map::new("total_stack_fee");
 
 #[cw_serde]
@@ -26,6 +66,16 @@ pub struct EraSnapshot {
     pub active: Uint128,
     pub restake_amount: Uint128,
     pub last_step_height: u64,
+    // liquidity buffer is part of active but never delegated
+    #[serde(default)]
+    pub liquidity_buffer: Uint128,
+    // platform fee skimmed from restake_amount in a native fee mode
+    #[serde(default)]
+    pub native_platform_fee: Uint128,
+}
+
+fn default_max_entries() -> u64 {
+    DEFAULT_MAX_ENTRIES
 }
 
 #[cw_serde]
@@ -63,6 +113,65 @@ pub struct PoolInfo {
     pub lsm_pending_limit: u64,
     pub rate_change_limit: Uint128,
     pub sdk_greater_or_equal_v047: bool,
+    #[serde(default)]
+    pub validator_select_strategy: ValidatorSelectStrategy,
+    #[serde(default)]
+    pub round_robin_index: u64,
+    #[serde(default)]
+    pub validator_weights: Vec<ValidatorWeight>,
+    #[serde(default)]
+    pub rebalance_tolerance: u64,
+    // zero would leave no unbond or redelegation entry for pools saved before the field
+    #[serde(default = "default_max_entries")]
+    pub max_entries: u64,
+    #[serde(default)]
+    pub slash_policy: SlashPolicy,
+    #[serde(default)]
+    pub slash_unstake_haircut: bool,
+    // jailed or not bonded validators from the last validators ICQ result
+    #[serde(default)]
+    pub inactive_validators: Vec<String>,
+    #[serde(default)]
+    pub auto_redelegate_inactive: bool,
+    // ibc_denom held on neutron for instant unstake
+    #[serde(default)]
+    pub liquidity_buffer: Uint128,
+    #[serde(default)]
+    pub liquidity_buffer_target: Uint128,
+    // share of stake deposits kept in the buffer, in CAL_BASE
+    #[serde(default)]
+    pub liquidity_buffer_ratio: Uint128,
+    // fee of instant unstake grows from min to max with the buffer utilization, in CAL_BASE
+    #[serde(default)]
+    pub instant_unstake_fee_min: Uint128,
+    #[serde(default)]
+    pub instant_unstake_fee_max: Uint128,
+    // cw721 contract minting a receipt for each unstake index, set once
+    #[serde(default)]
+    pub unstake_nft: Option<Addr>,
+    // share of the withdrawn amount a WithdrawFor keeper may take, in CAL_BASE
+    #[serde(default)]
+    pub withdraw_for_tip: Uint128,
+    // host chain side of the transfer channel to neutron, empty disables withdraw to neutron
+    #[serde(default)]
+    pub host_transfer_channel_id: String,
+    // ica and transfer packet timeouts, tx types not listed use DEFAULT_TIMEOUT_SECONDS
+    #[serde(default)]
+    pub tx_timeouts: Vec<TxTimeout>,
+    // denoms besides remote_denom tracked on the withdraw ica, e.g. ibc rewards or airdrops
+    #[serde(default)]
+    pub extra_reward_denoms: Vec<String>,
+    #[serde(default)]
+    pub extra_reward_route: ExtraRewardRoute,
+    #[serde(default)]
+    pub fee_mode: FeeMode,
+    #[serde(default)]
+    pub unbond_commission_schedule: UnbondCommissionSchedule,
+    // remote_denom paid to the platform in a native fee mode, counted once the rebond tx is
+    // acked. a NativeNeutron transfer refunded later is still counted here, the refund stays
+    // as undelegated balance of the pool ica and is not restaked
+    #[serde(default)]
+    pub total_native_platform_fee: Uint128,
 }
 
 pub const POOLS: Map<String, PoolInfo> = Map::new("pools");
@@ -83,6 +192,154 @@ pub enum EraStatus {
     ActiveEnded,
 }
 
+// commission of unstake by hold time or size, unbond_commission applies when no tier matches
+#[cw_serde]
+#[derive(Default)]
+pub enum UnbondCommissionSchedule {
+    #[default]
+    Flat,
+    // the tier with the largest min_hold_seconds reached, ascending
+    HoldTime {
+        tiers: Vec<HoldTimeTier>,
+    },
+    // the tier with the largest min_lsd_token_amount reached, ascending
+    Size {
+        tiers: Vec<SizeTier>,
+    },
+    // falls linearly from initial_commission to unbond_commission over decay_seconds
+    Decay {
+        initial_commission: Uint128,
+        decay_seconds: u64,
+    },
+}
+
+#[cw_serde]
+pub struct HoldTimeTier {
+    pub min_hold_seconds: u64,
+    pub commission: Uint128,
+}
+
+#[cw_serde]
+pub struct SizeTier {
+    pub min_lsd_token_amount: Uint128,
+    pub commission: Uint128,
+}
+
+#[cw_serde]
+pub struct StakeRecord {
+    // lsd tokens minted to the user and not yet unstaked
+    pub lsd_token_amount: Uint128,
+    // amount weighted average stake time
+    pub staked_at: u64,
+}
+
+// (userAddress, poolAddress) => StakeRecord
+pub const STAKE_RECORDS: Map<(Addr, String), StakeRecord> = Map::new("stake_records");
+
+// how the platform fee is paid, the stack fee is always minted as lsd tokens
+#[cw_serde]
+#[derive(Default)]
+pub enum FeeMode {
+    // mint lsd tokens to platform_fee_receiver
+    #[default]
+    LsdToken,
+    // skim remote_denom from restake_amount and send it to a host chain address
+    NativeHostChain {
+        receiver: String,
+    },
+    // skim remote_denom from restake_amount and transfer it to platform_fee_receiver
+    // over host_transfer_channel_id
+    NativeNeutron,
+}
+
+// where the extra reward denoms collected on the withdraw ica go each era
+#[cw_serde]
+#[derive(Default)]
+pub enum ExtraRewardRoute {
+    // left on the withdraw ica
+    #[default]
+    Keep,
+    // sent to a host chain address
+    Treasury {
+        receiver: String,
+    },
+    // sent as funds of a host chain contract call, which should pay remote_denom back
+    // to the withdraw ica so the next era restakes it
+    Swap {
+        contract: String,
+        msg: Binary,
+    },
+}
+
+#[cw_serde]
+pub struct TxTimeout {
+    pub tx_type: TxType,
+    pub timeout_seconds: u64,
+}
+
+// weight in basis points, weights of a pool sum up to WEIGHT_BASE
+#[cw_serde]
+pub struct ValidatorWeight {
+    pub validator: String,
+    pub weight: u64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum ValidatorSelectStrategy {
+    // split evenly across all pool validators, or by weight if weights are configured
+    #[default]
+    Average,
+    // delegate all to the validator with the lowest delegation per weight
+    LowestDelegatedFirst,
+    // split toward target weights (equal if not configured), the most under-delegated validator gets the most
+    EqualizeTowardTarget,
+    // delegate all to the next validator with a weight in turn
+    RoundRobin,
+}
+
+#[cw_serde]
+pub enum EraProcessStep {
+    EraUpdate,
+    EraBond,
+    EraCollectWithdraw,
+    EraRebond,
+    EraActive,
+}
+
+#[cw_serde]
+pub enum EraProcessWait {
+    // current era has already been processed
+    NextEra {
+        current_era: u64,
+    },
+    // validator set change is not finished yet
+    ValidatorUpdate,
+    // ICA tx or ibc transfer sent by the previous step is waiting for its ack
+    TxAck,
+    // ICQ result has not been submitted after the last step
+    IcqUpdate {
+        query_kind: QueryKind,
+        last_step_height: u64,
+        last_submitted_local_height: u64,
+    },
+    // LSM share tokens need to be redeemed before era active
+    PendingShareTokens,
+    // the pool is paused, e.g. after a slash, until the admin resumes it
+    PoolPaused,
+}
+
+// data of EraProcess response
+#[cw_serde]
+pub struct EraProcessResponse {
+    pub pool_addr: String,
+    pub era: u64,
+    pub status: EraStatus,
+    pub executed_step: Option<EraProcessStep>,
+    pub next_step: EraProcessStep,
+    pub wait: Option<EraProcessWait>,
+}
+
 #[cw_serde]
 pub enum ValidatorUpdateStatus {
     Start,
@@ -90,10 +347,20 @@ pub enum ValidatorUpdateStatus {
     End,
 }
 
+#[cw_serde]
+pub enum WithdrawMode {
+    // bank send to a host chain address
+    HostChain,
+    // ics20 transfer back to a neutron address over host_transfer_channel_id
+    Neutron,
+}
+
 #[cw_serde]
 pub enum WithdrawStatus {
     Default,
     Pending,
+    // the ica sent the transfer to neutron, it is delivered or refunded by transfer_deadline
+    Transferring,
 }
 
 #[cw_serde]
@@ -104,10 +371,66 @@ pub struct UnstakeInfo {
     pub amount: Uint128,
     pub status: WithdrawStatus,
     pub index: u64,
+    // the owner of this token may withdraw instead of the unstaker
+    #[serde(default)]
+    pub nft: Option<Addr>,
+    #[serde(default)]
+    pub transfer_deadline: Option<u64>,
+}
+
+impl WithdrawStatus {
+    pub fn index_key(&self) -> u8 {
+        match self {
+            WithdrawStatus::Default => 0,
+            WithdrawStatus::Pending => 1,
+            WithdrawStatus::Transferring => 2,
+        }
+    }
+}
+
+pub struct UnstakeIndexes<'a> {
+    // (poolAddress,unstaker)
+    pub unstaker: MultiIndex<'a, (String, String), UnstakeInfo, (String, u64)>,
+    // (poolAddress,status)
+    pub status: MultiIndex<'a, (String, u8), UnstakeInfo, (String, u64)>,
+}
+
+impl<'a> IndexList<UnstakeInfo> for UnstakeIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<UnstakeInfo>> + '_> {
+        let v: Vec<&dyn Index<UnstakeInfo>> = vec![&self.unstaker, &self.status];
+        Box::new(v.into_iter())
+    }
 }
 
 // (poolAddress,unstakeIndex)
-pub const UNSTAKES_OF_INDEX: Map<(String, u64), UnstakeInfo> = Map::new("unstakes_of_index");
+pub fn unstakes_of_index<'a>() -> IndexedMap<'a, (String, u64), UnstakeInfo, UnstakeIndexes<'a>> {
+    let indexes = UnstakeIndexes {
+        unstaker: MultiIndex::new(
+            |_pk, d: &UnstakeInfo| (d.pool_addr.clone(), d.unstaker.clone()),
+            "unstakes_of_index",
+            "unstakes_of_index__unstaker",
+        ),
+        status: MultiIndex::new(
+            |_pk, d: &UnstakeInfo| (d.pool_addr.clone(), d.status.index_key()),
+            "unstakes_of_index",
+            "unstakes_of_index__status",
+        ),
+    };
+    IndexedMap::new("unstakes_of_index", indexes)
+}
+
+// unstakes saved before the indexes existed only have the primary entry, so save them again
+pub fn migrate_unstake_indexes(storage: &mut dyn Storage) -> StdResult<u64> {
+    let unstakes = unstakes_of_index()
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let count = unstakes.len() as u64;
+    for (key, unstake_info) in unstakes {
+        unstakes_of_index().replace(storage, key, Some(&unstake_info), None)?;
+    }
+
+    Ok(count)
+}
 
 #[cw_serde]
 pub struct DelegatorDelegationsResponse {
@@ -151,6 +474,9 @@ pub struct IcaInfo {
     pub host_channel_id: String,
     pub ctrl_port_id: String,
     pub ica_addr: String,
+    // the ordered channel is closed by a packet timeout until ReopenChannel gets an OpenAck
+    #[serde(default)]
+    pub closed: bool,
 }
 
 //  key: ica id value: (pool IcaInfo, withdraw icaInfo, admin)
@@ -160,6 +486,9 @@ pub const INFO_OF_ICA_ID: Map<String, (IcaInfo, IcaInfo, Addr)> = Map::new("info
 pub const UNSTAKES_INDEX_FOR_USER: Map<(Addr, String), Vec<u64>> =
     Map::new("unstakes_index_for_user");
 
+// (userAddress,poolAddress) => host chain receiver used by WithdrawFor
+pub const WITHDRAW_RECEIVERS: Map<(Addr, String), String> = Map::new("withdraw_receivers");
+
 // contains query kinds that we expect to handle in `sudo_kv_query_result`
 #[cw_serde]
 pub enum QueryKind {
@@ -224,10 +553,69 @@ pub enum TxType {
     EraRebond,
     RedeemTokenForShare,
     StakeLsm,
+    PoolRebalance,
+    RedelegateInactive,
+    WithdrawFor,
+    UserWithdrawToNeutron,
 }
+// bump when the layout of SudoPayload or SudoData changes and migrate in-flight payloads
+pub const SUDO_PAYLOAD_VERSION: u64 = 1;
+
+// data needed by the callback of each TxType
+#[cw_serde]
+pub enum SudoData {
+    Empty {},
+    SetWithdrawAddr {
+        withdraw_addr: String,
+    },
+    EraBond {
+        unbond_validators: Vec<String>,
+    },
+    UpdateValidator {
+        new_validators: Vec<String>,
+        // [(src validator,dst validator)] recorded on ack
+        #[serde(default)]
+        redelegating_pairs: Vec<(String, String)>,
+    },
+    RmValidator {
+        validator: String,
+        #[serde(default)]
+        redelegating_pairs: Vec<(String, String)>,
+    },
+    Redelegate {
+        redelegating_pairs: Vec<(String, String)>,
+    },
+    RedeemTokenForShare {
+        denoms: Vec<String>,
+    },
+    StakeLsm {
+        staker: String,
+        token_amount: Uint128,
+        share_token_amount: Uint128,
+        share_token_ibc_denom: String,
+        share_token_denom: String,
+    },
+    UserWithdraw {
+        amount: Uint128,
+        user: String,
+        receiver: String,
+        unstake_index_list: Vec<u64>,
+        // timeout of the ics-20 transfer, zero for a withdraw on the host chain
+        #[serde(default)]
+        transfer_deadline: u64,
+    },
+    WithdrawFor {
+        amount: Uint128,
+        keeper: String,
+        tip: Uint128,
+        unstake_index_list: Vec<u64>,
+    },
+}
+
 #[cw_serde]
 pub struct SudoPayload {
-    pub message: String,
+    pub version: u64,
+    pub data: SudoData,
     pub pool_addr: String,
     pub port_id: String,
     pub tx_type: TxType,
@@ -241,7 +629,7 @@ pub fn save_reply_payload(store: &mut dyn Storage, payload: SudoPayload) -> StdR
 }
 pub fn read_reply_payload(store: &dyn Storage, id: u64) -> StdResult<SudoPayload> {
     let data = REPLY_ID_TO_PAYLOAD.load(store, id)?;
-    from_json(Binary(data))
+    check_sudo_payload_version(from_json(Binary(data))?)
 }
 
 /// SUDO_PAYLOAD - tmp storage for sudo handler payloads
@@ -250,6 +638,35 @@ pub fn read_reply_payload(store: &dyn Storage, id: u64) -> StdResult<SudoPayload
 /// we can catch the counter in the reply msg for outgoing sudo msg
 /// and save our payload for the msg
 pub const SUDO_PAYLOAD: Map<(String, u64), Vec<u8>> = Map::new("sudo_payload");
+// (channel_id, seq_id) => block time in seconds the tx was submitted
+pub const SUDO_PAYLOAD_TIME: Map<(String, u64), u64> = Map::new("sudo_payload_time");
+// (channel_id, seq_id) => block time in seconds the tx times out, fixed when it was submitted
+pub const SUDO_PAYLOAD_DEADLINE: Map<(String, u64), u64> = Map::new("sudo_payload_deadline");
+
+#[cw_serde]
+pub enum PendingTxOutcome {
+    Success,
+    Failed,
+}
+
+#[cw_serde]
+pub enum TransferOutcome {
+    Delivered,
+    Refunded,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct PendingTx {
+    pub channel_id: String,
+    pub seq_id: u64,
+    pub payload: SudoPayload,
+    // none for payloads submitted before the time was recorded
+    pub submitted_at: Option<u64>,
+    pub age: Option<u64>,
+    // none for payloads submitted before the deadline was recorded
+    pub deadline: Option<u64>,
+}
 pub fn save_sudo_payload(
     store: &mut dyn Storage,
     channel_id: String,
@@ -258,13 +675,28 @@ pub fn save_sudo_payload(
 ) -> StdResult<()> {
     SUDO_PAYLOAD.save(store, (channel_id, seq_id), &to_json_vec(&payload)?)
 }
+pub fn remove_sudo_payload(store: &mut dyn Storage, channel_id: String, seq_id: u64) {
+    SUDO_PAYLOAD.remove(store, (channel_id.clone(), seq_id));
+    SUDO_PAYLOAD_TIME.remove(store, (channel_id.clone(), seq_id));
+    SUDO_PAYLOAD_DEADLINE.remove(store, (channel_id, seq_id));
+}
 pub fn read_sudo_payload(
     store: &dyn Storage,
     channel_id: String,
     seq_id: u64,
 ) -> StdResult<SudoPayload> {
     let data = SUDO_PAYLOAD.load(store, (channel_id, seq_id))?;
-    from_json(Binary(data))
+    check_sudo_payload_version(from_json(Binary(data))?)
+}
+
+fn check_sudo_payload_version(payload: SudoPayload) -> StdResult<SudoPayload> {
+    if payload.version != SUDO_PAYLOAD_VERSION {
+        return Err(StdError::generic_err(format!(
+            "unsupported sudo payload version: {}",
+            payload.version
+        )));
+    }
+    Ok(payload)
 }
 
 // key: (ica address, query kind) value: query reply id
@@ -282,5 +714,330 @@ pub const QUERY_ID_TO_REPLY_ID: Map<u64, u64> = Map::new("query_id_to_reply_id")
 pub const VALIDATORS_UNBONDS_TIME: Map<(String, String), Vec<u64>> =
     Map::new("validators_unbonds_time");
 
+// (pool,src validator,dst validator) -> vec[completion timestamp]
+pub const VALIDATORS_REDELEGATIONS_TIME: Map<(String, String, String), Vec<u64>> =
+    Map::new("validators_redelegations_time");
+
+#[cw_serde]
+pub enum RedelegationLimit {
+    // the source validator has an immature incoming redelegation
+    TransitiveRedelegation {
+        src_validator: String,
+        until: u64,
+    },
+    // the (src, dst) pair already has max entries of immature redelegations
+    MaxEntries {
+        max_entries: u64,
+        next_free_time: u64,
+    },
+}
+
+// for rpc query
+#[cw_serde]
+pub struct SimulateUnstakeResponse {
+    pub held_seconds: u64,
+    // amount weighted over the aged and untracked lsd tokens
+    pub commission: Uint128,
+    // lsd tokens sent to platform_fee_receiver
+    pub fee: Uint128,
+    pub burn_lsd_token_amount: Uint128,
+    pub token_amount: Uint128,
+    pub receive_amount: Uint128,
+    // era from which the unstake can be withdrawn
+    pub withdraw_era: u64,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct IcqHealth {
+    pub query_kind: QueryKind,
+    pub addr: String,
+    // none if the query is not registered
+    pub last_submitted_local_height: Option<u64>,
+    // submitted after era_snapshot.last_step_height
+    pub fresh: bool,
+}
+
+// for rpc query
+#[cw_serde]
+pub enum PoolNextAction {
+    InitPool,
+    ReopenChannel,
+    // a step tx is in flight, see pending_txs
+    WaitForTx,
+    WaitForValidatorUpdate,
+    Unpause,
+    WaitForEra { era: u64 },
+    WaitForIcq { query_kind: QueryKind },
+    RedeemTokenForShare,
+    EraUpdate,
+    EraBond,
+    EraCollectWithdraw,
+    EraRebond,
+    // may still fail with RateChangeOverLimit
+    EraActive,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct PoolHealthResponse {
+    pub status: EraStatus,
+    pub validator_update_status: ValidatorUpdateStatus,
+    pub paused: bool,
+    pub era: u64,
+    pub current_era: u64,
+    pub last_step_height: u64,
+    pub delegations_icq: IcqHealth,
+    pub withdraw_balances_icq: IcqHealth,
+    pub closed_channel_ids: Vec<String>,
+    pub share_tokens: Vec<Coin>,
+    pub redeemming_share_token_denom: Vec<String>,
+    pub pending_txs: Vec<PendingTx>,
+    pub next_action: PoolNextAction,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct RoleMember {
+    pub account: Addr,
+    pub roles: Vec<Role>,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct PoolSummary {
+    pub pool_addr: String,
+    // listed in stack pools
+    pub entrusted: bool,
+    pub status: EraStatus,
+    pub era: u64,
+    pub rate: Uint128,
+    pub active: Uint128,
+    pub bond: Uint128,
+    pub unbond: Uint128,
+    pub lsd_token: Addr,
+    pub paused: bool,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct EraRateEntry {
+    pub era: u64,
+    pub rate: Uint128,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct AprResponse {
+    pub start_era: u64,
+    pub start_rate: Uint128,
+    pub end_era: u64,
+    pub end_rate: Uint128,
+    // annualized rate growth, based on CAL_BASE
+    pub apr: Uint128,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct SimulateStakeResponse {
+    pub token_amount: Uint128,
+    // kept in the liquidity buffer for instant unstake
+    pub buffer_amount: Uint128,
+    pub bond_amount: Uint128,
+    pub lsd_token_amount: Uint128,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct SimulateWithdrawResponse {
+    pub amount: Uint128,
+    pub withdrawable_unstake_index_list: Vec<u64>,
+    // earliest era one of the unstakes not yet withdrawable matures
+    pub earliest_withdraw_era: Option<u64>,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct PendingRedelegation {
+    pub src_validator: String,
+    pub dst_validator: String,
+    pub completion_times: Vec<u64>,
+    pub free_entries: u64,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct LockedValidator {
+    pub validator: String,
+    pub until: u64,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct RedelegationLimitsResponse {
+    // immature redelegation entries allowed per (src, dst) pair on the host chain
+    pub max_entries: u64,
+    pub redelegation_seconds: u64,
+    pub pending_redelegations: Vec<PendingRedelegation>,
+    // validators with immature incoming redelegations, they can't be redelegated from until then
+    pub locked_validators: Vec<LockedValidator>,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct ValidatorUnbondSlot {
+    pub validator: String,
+    pub used_entries: u64,
+    pub free_slots: u64,
+    // when the oldest immature unbonding entry matures, none if no entry is used
+    pub next_free_time: Option<u64>,
+}
+
+// for rpc query
+#[cw_serde]
+pub struct ValidatorUnbondSlotsResponse {
+    // immature unbonding entries allowed per validator on the host chain
+    pub max_entries: u64,
+    pub unbonding_seconds: u64,
+    pub validators: Vec<ValidatorUnbondSlot>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum SlashPolicy {
+    // the loss lowers the rate of the era
+    #[default]
+    Socialize,
+    // the pool is paused in era active until the admin acknowledges the loss
+    PauseAndWait,
+}
+
+// pool -> [(validator, delegated amount)] expected once the era's delegate and undelegate txs land
+pub const EXPECTED_DELEGATIONS: Map<String, Vec<(String, Uint128)>> =
+    Map::new("expected_delegations");
+
+#[cw_serde]
+pub struct ValidatorSlash {
+    pub validator: String,
+    pub expected: Uint128,
+    pub actual: Uint128,
+    pub loss: Uint128,
+}
+
+#[cw_serde]
+pub struct SlashEvent {
+    pub era: u64,
+    pub loss: Uint128,
+    pub validators: Vec<ValidatorSlash>,
+    pub policy: SlashPolicy,
+    // total amount cut from the pending unstakes
+    pub unstake_haircut: Uint128,
+    pub rate_before: Uint128,
+    pub rate_after: Uint128,
+    // the admin accepted the loss of a pause and wait slash, era active goes on
+    #[serde(default)]
+    pub acknowledged: bool,
+}
+
+// (pool, era) -> slash event
+pub const SLASH_EVENTS: Map<(String, u64), SlashEvent> = Map::new("slash_events");
+
+#[cw_serde]
+pub enum ValidatorStatusEventKind {
+    Jailed,
+    Unbonding,
+    Unbonded,
+    Reactivated,
+    Redelegated,
+}
+
+impl ValidatorStatusEventKind {
+    pub fn event_type(&self) -> String {
+        match self {
+            ValidatorStatusEventKind::Jailed => "validator_jailed".to_string(),
+            ValidatorStatusEventKind::Unbonding => "validator_unbonding".to_string(),
+            ValidatorStatusEventKind::Unbonded => "validator_unbonded".to_string(),
+            ValidatorStatusEventKind::Reactivated => "validator_reactivated".to_string(),
+            ValidatorStatusEventKind::Redelegated => "validator_redelegated".to_string(),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct ValidatorStatusEvent {
+    pub era: u64,
+    pub validator: String,
+    pub kind: ValidatorStatusEventKind,
+    // stake moved away from the validator, only for redelegated
+    pub amount: Uint128,
+}
+
+// (pool, era) -> validator status events of the era
+pub const VALIDATOR_STATUS_EVENTS: Map<(String, u64), Vec<ValidatorStatusEvent>> =
+    Map::new("validator_status_events");
+
 // (pool, era) -> rate
 pub const ERA_RATE: Map<(String, u64), Uint128> = Map::new("era_rate");
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use cosmwasm_std::from_json;
+
+    // a pool as stored by the release before the upgrade
+    pub(crate) const BASELINE_POOL_INFO: &[u8] = br#"{
+        "bond": "10", "unbond": "5", "active": "100",
+        "lsd_token": "lsd", "ica_id": "ica", "ibc_denom": "ibc/atom",
+        "channel_id_of_ibc_denom": "channel-0", "remote_denom": "uatom",
+        "validator_addrs": ["val"], "era": 3, "rate": "1000000",
+        "era_seconds": 86400, "offset": -10, "minimal_stake": "1",
+        "unstake_times_limit": 10, "next_unstake_index": 2, "unbonding_period": 4,
+        "status": "active_ended", "validator_update_status": "end",
+        "unbond_commission": "0", "platform_fee_commission": "100000",
+        "total_platform_fee": "0", "total_lsd_token_amount": "100",
+        "platform_fee_receiver": "receiver", "admin": "admin",
+        "share_tokens": [], "redeemming_share_token_denom": [],
+        "era_snapshot": {
+            "era": 3, "bond": "0", "unbond": "0", "active": "100",
+            "restake_amount": "0", "last_step_height": 7
+        },
+        "paused": false, "lsm_support": false, "lsm_pending_limit": 0,
+        "rate_change_limit": "0", "sdk_greater_or_equal_v047": true
+    }"#;
+
+    #[test]
+    fn load_pool_info_saved_before_upgrade() -> StdResult<()> {
+        let pool_info: PoolInfo = from_json(BASELINE_POOL_INFO)?;
+
+        assert_eq!(pool_info.active, Uint128::new(100));
+        assert_eq!(
+            pool_info.validator_select_strategy,
+            ValidatorSelectStrategy::Average
+        );
+        assert_eq!(pool_info.slash_policy, SlashPolicy::Socialize);
+        assert_eq!(pool_info.fee_mode, FeeMode::LsdToken);
+        assert_eq!(pool_info.extra_reward_route, ExtraRewardRoute::Keep);
+        assert_eq!(
+            pool_info.unbond_commission_schedule,
+            UnbondCommissionSchedule::Flat
+        );
+        assert_eq!(pool_info.unstake_nft, None);
+        assert_eq!(pool_info.max_entries, DEFAULT_MAX_ENTRIES);
+        assert!(pool_info.tx_timeouts.is_empty());
+        assert_eq!(pool_info.era_snapshot.liquidity_buffer, Uint128::zero());
+        assert_eq!(pool_info.era_snapshot.native_platform_fee, Uint128::zero());
+        Ok(())
+    }
+
+    #[test]
+    fn load_unstake_info_saved_before_upgrade() -> StdResult<()> {
+        let unstake_info: UnstakeInfo = from_json(
+            br#"{"era": 1, "pool_addr": "pool", "unstaker": "user", "amount": "10",
+                "status": "default", "index": 0}"#,
+        )?;
+
+        assert_eq!(unstake_info.nft, None);
+        assert_eq!(unstake_info.transfer_deadline, None);
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
 use crate::execute_era_rebond::sudo_era_rebond_failed_callback;
+use crate::execute_pool_rebalance::{
+    sudo_pool_rebalance_callback, sudo_pool_rebalance_failed_callback,
+};
 use crate::execute_pool_update_validator::{
     sudo_update_validator_callback, sudo_update_validator_failed_callback,
 };
@@ -7,10 +10,18 @@ use crate::execute_redeem_token_for_share::{
 };
 use crate::execute_stake_lsm::{sudo_stake_lsm_callback, sudo_stake_lsm_failed_callback};
 use crate::execute_withdraw::{sudo_withdraw_callback, sudo_withdraw_failed_callback};
-use crate::helper::sudo_set_withdraw_addr_failed_callback;
+use crate::execute_withdraw_for::{sudo_withdraw_for_callback, sudo_withdraw_for_failed_callback};
+use crate::helper::{
+    parse_ica_port_id, sudo_set_withdraw_addr_failed_callback, tx_timeout_seconds,
+    DEFAULT_TIMEOUT_SECONDS,
+};
 use crate::state::{
-    read_reply_payload, read_sudo_payload, save_reply_payload, save_sudo_payload, SudoPayload,
-    TxType, SUDO_PAYLOAD,
+    read_reply_payload, read_sudo_payload, remove_sudo_payload, save_reply_payload,
+    save_sudo_payload, SudoPayload, TxType, INFO_OF_ICA_ID, POOLS, SUDO_PAYLOAD_DEADLINE,
+    SUDO_PAYLOAD_TIME,
+};
+use crate::validator_health::{
+    sudo_redelegate_inactive_callback, sudo_redelegate_inactive_failed_callback,
 };
 use crate::{error_conversion::ContractError, execute_era_rebond::sudo_era_rebond_callback};
 use crate::{
@@ -27,7 +38,8 @@ use crate::{
     helper::sudo_set_withdraw_addr_callback,
 };
 use cosmwasm_std::{
-    from_json, Binary, CosmosMsg, DepsMut, Env, Reply, Response, StdError, StdResult, SubMsg,
+    from_json, Binary, CosmosMsg, DepsMut, Env, Reply, Response, StdError, StdResult, Storage,
+    SubMsg,
 };
 use neutron_sdk::sudo::msg::RequestPacket;
 use neutron_sdk::{
@@ -52,7 +64,7 @@ pub fn msg_with_sudo_callback<C: Into<CosmosMsg<T>>, T>(
 // The method is used to extract sequence id and channel from SubmitTxResponse to process sudo payload defined in msg_with_sudo_callback later in Sudo handler.
 // Such flow msg_with_sudo_callback() -> reply() -> prepare_sudo_payload() -> sudo() allows you "attach" some payload to your Transfer message
 // and process this payload when an acknowledgement for the SubmitTx message is received in Sudo handler
-pub fn prepare_sudo_payload(mut deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+pub fn prepare_sudo_payload(mut deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
     let payload = read_reply_payload(deps.storage, msg.id)?;
 
     let resp: MsgIbcTransferResponse = from_json(
@@ -66,7 +78,22 @@ pub fn prepare_sudo_payload(mut deps: DepsMut, _env: Env, msg: Reply) -> StdResu
 
     let seq_id = resp.sequence_id;
     let channel_id = resp.channel;
-    save_sudo_payload(deps.branch().storage, channel_id, seq_id, payload)?;
+    // the tx was submitted in this block with the timeout of the pool in force now
+    let timeout_seconds = match POOLS.may_load(deps.storage, payload.pool_addr.clone())? {
+        Some(pool_info) => tx_timeout_seconds(&pool_info, &payload.tx_type),
+        None => DEFAULT_TIMEOUT_SECONDS,
+    };
+    save_sudo_payload(deps.branch().storage, channel_id.clone(), seq_id, payload)?;
+    SUDO_PAYLOAD_TIME.save(
+        deps.storage,
+        (channel_id.clone(), seq_id),
+        &env.block.time.seconds(),
+    )?;
+    SUDO_PAYLOAD_DEADLINE.save(
+        deps.storage,
+        (channel_id, seq_id),
+        &(env.block.time.seconds() + timeout_seconds),
+    )?;
     Ok(Response::new())
 }
 
@@ -84,7 +111,7 @@ pub fn sudo_response(
         .ok_or_else(|| ContractError::CallBackErrChannelIDNotFound {})?;
 
     if let Ok(payload) = read_sudo_payload(deps.storage, channel_id.clone(), seq_id) {
-        SUDO_PAYLOAD.remove(deps.storage, (channel_id, seq_id));
+        remove_sudo_payload(deps.storage, channel_id, seq_id);
         return sudo_callback(deps, env, payload);
     }
 
@@ -104,13 +131,42 @@ pub fn sudo_error(
         .ok_or_else(|| ContractError::CallBackErrChannelIDNotFound {})?;
 
     if let Ok(payload) = read_sudo_payload(deps.storage, channel_id.clone(), seq_id) {
-        SUDO_PAYLOAD.remove(deps.storage, (channel_id, seq_id));
+        remove_sudo_payload(deps.storage, channel_id, seq_id);
         return sudo_failed_callback(deps, payload);
     }
 
     Err(ContractError::CallBackErrErrorMsg {}.into())
 }
 
+// a timeout closes the ordered ica channel, mark it so ica txs are blocked until it is reopened
+fn close_ica_channel(
+    storage: &mut dyn Storage,
+    port_id: &str,
+    channel_id: &str,
+) -> NeutronResult<bool> {
+    let Some((ica_id, is_withdraw)) = parse_ica_port_id(port_id) else {
+        return Ok(false);
+    };
+    let Some((mut pool_ica_info, mut withdraw_ica_info, admin)) =
+        INFO_OF_ICA_ID.may_load(storage, ica_id.clone())?
+    else {
+        return Ok(false);
+    };
+
+    let ica_info = if is_withdraw {
+        &mut withdraw_ica_info
+    } else {
+        &mut pool_ica_info
+    };
+    if ica_info.ctrl_channel_id != channel_id {
+        return Ok(false);
+    }
+    ica_info.closed = true;
+
+    INFO_OF_ICA_ID.save(storage, ica_id, &(pool_ica_info, withdraw_ica_info, admin))?;
+    Ok(true)
+}
+
 pub fn sudo_timeout(deps: DepsMut, req: RequestPacket) -> NeutronResult<Response<NeutronMsg>> {
     let seq_id = req
         .sequence
@@ -119,15 +175,30 @@ pub fn sudo_timeout(deps: DepsMut, req: RequestPacket) -> NeutronResult<Response
         .source_channel
         .ok_or_else(|| ContractError::CallBackErrChannelIDNotFound {})?;
 
+    let closed = match req.source_port {
+        Some(port_id) => close_ica_channel(deps.storage, &port_id, &channel_id)?,
+        None => false,
+    };
+
+    // the failed callback reverts the timed out step, it is run again once the channel is reopened
     if let Ok(payload) = read_sudo_payload(deps.storage, channel_id.clone(), seq_id) {
-        SUDO_PAYLOAD.remove(deps.storage, (channel_id, seq_id));
-        return sudo_failed_callback(deps, payload);
+        remove_sudo_payload(deps.storage, channel_id.clone(), seq_id);
+        let res = sudo_failed_callback(deps, payload)?;
+        return Ok(if closed {
+            res.add_attribute("closed_channel_id", channel_id)
+        } else {
+            res
+        });
+    }
+
+    if closed {
+        return Ok(Response::new().add_attribute("closed_channel_id", channel_id));
     }
 
     Err(ContractError::CallBackErrErrorMsg {}.into())
 }
 
-fn sudo_callback(
+pub fn sudo_callback(
     deps: DepsMut,
     env: Env,
     payload: SudoPayload,
@@ -138,15 +209,20 @@ fn sudo_callback(
         TxType::EraBond => sudo_era_bond_callback(deps, env, payload),
         TxType::EraCollectWithdraw => sudo_era_collect_withdraw_callback(deps, env, payload),
         TxType::EraRebond => sudo_era_rebond_callback(deps, env, payload),
-        TxType::UserWithdraw => sudo_withdraw_callback(deps, payload),
-        TxType::UpdateValidator => sudo_update_validator_callback(deps, payload),
-        TxType::RmValidator => sudo_rm_validator_callback(deps, payload),
-        TxType::StakeLsm => sudo_stake_lsm_callback(deps, payload),
+        TxType::UserWithdraw | TxType::UserWithdrawToNeutron => {
+            sudo_withdraw_callback(deps, payload)
+        }
+        TxType::UpdateValidator => sudo_update_validator_callback(deps, env, payload),
+        TxType::RmValidator => sudo_rm_validator_callback(deps, env, payload),
+        TxType::StakeLsm => sudo_stake_lsm_callback(deps, env, payload),
         TxType::RedeemTokenForShare => sudo_redeem_token_for_share_callback(deps, payload),
+        TxType::PoolRebalance => sudo_pool_rebalance_callback(deps, env, payload),
+        TxType::RedelegateInactive => sudo_redelegate_inactive_callback(deps, env, payload),
+        TxType::WithdrawFor => sudo_withdraw_for_callback(deps, payload),
     }
 }
 
-fn sudo_failed_callback(
+pub fn sudo_failed_callback(
     deps: DepsMut,
     payload: SudoPayload,
 ) -> NeutronResult<Response<NeutronMsg>> {
@@ -156,10 +232,117 @@ fn sudo_failed_callback(
         TxType::EraBond => sudo_era_bond_failed_callback(deps, payload),
         TxType::EraCollectWithdraw => sudo_era_collect_withdraw_failed_callback(deps, payload),
         TxType::EraRebond => sudo_era_rebond_failed_callback(deps, payload),
-        TxType::UserWithdraw => sudo_withdraw_failed_callback(deps, payload),
+        TxType::UserWithdraw | TxType::UserWithdrawToNeutron => {
+            sudo_withdraw_failed_callback(deps, payload)
+        }
         TxType::UpdateValidator => sudo_update_validator_failed_callback(deps, payload),
         TxType::RmValidator => sudo_rm_validator_failed_callback(deps, payload),
         TxType::StakeLsm => sudo_stake_lsm_failed_callback(deps, payload),
         TxType::RedeemTokenForShare => sudo_redeem_token_for_share_failed_callback(deps, payload),
+        TxType::PoolRebalance => sudo_pool_rebalance_failed_callback(deps, payload),
+        TxType::RedelegateInactive => sudo_redelegate_inactive_failed_callback(deps, payload),
+        TxType::WithdrawFor => sudo_withdraw_for_failed_callback(deps, payload),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{
+        tests::BASELINE_POOL_INFO, EraStatus, IcaInfo, PoolInfo, SudoData, POOLS,
+        SUDO_PAYLOAD_VERSION,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{Addr, OwnedDeps};
+
+    const POOL_ADDR: &str = "pool_ica_addr";
+    const PORT_ID: &str = "icacontroller-stake_manager.ica";
+    const CHANNEL_ID: &str = "channel-1";
+
+    fn ica_info(channel_id: &str) -> IcaInfo {
+        IcaInfo {
+            ctrl_connection_id: "connection-0".to_string(),
+            host_connection_id: "connection-1".to_string(),
+            ctrl_channel_id: channel_id.to_string(),
+            host_channel_id: "channel-9".to_string(),
+            ctrl_port_id: PORT_ID.to_string(),
+            ica_addr: POOL_ADDR.to_string(),
+            closed: false,
+        }
+    }
+
+    fn setup() -> StdResult<OwnedDeps<MockStorage, MockApi, MockQuerier>> {
+        let mut deps = mock_dependencies();
+        let pool_info: PoolInfo = from_json(BASELINE_POOL_INFO)?;
+        POOLS.save(deps.as_mut().storage, POOL_ADDR.to_string(), &pool_info)?;
+        INFO_OF_ICA_ID.save(
+            deps.as_mut().storage,
+            "ica".to_string(),
+            &(
+                ica_info(CHANNEL_ID),
+                ica_info("channel-2"),
+                Addr::unchecked("admin"),
+            ),
+        )?;
+        save_sudo_payload(
+            deps.as_mut().storage,
+            CHANNEL_ID.to_string(),
+            7,
+            SudoPayload {
+                version: SUDO_PAYLOAD_VERSION,
+                data: SudoData::SetWithdrawAddr {
+                    withdraw_addr: "withdraw_ica_addr".to_string(),
+                },
+                pool_addr: POOL_ADDR.to_string(),
+                port_id: PORT_ID.to_string(),
+                tx_type: TxType::SetWithdrawAddr,
+            },
+        )?;
+        Ok(deps)
+    }
+
+    fn request() -> RequestPacket {
+        RequestPacket {
+            sequence: Some(7),
+            source_port: Some(PORT_ID.to_string()),
+            source_channel: Some(CHANNEL_ID.to_string()),
+            destination_port: None,
+            destination_channel: None,
+            data: None,
+            timeout_height: None,
+            timeout_timestamp: None,
+        }
+    }
+
+    #[test]
+    fn error_ack_runs_failed_callback_and_keeps_channel_open() -> NeutronResult<()> {
+        let mut deps = setup()?;
+
+        let res = sudo_error(deps.as_mut(), request(), "error".to_string())?;
+
+        assert!(res.attributes.is_empty());
+        let pool_info = POOLS.load(deps.as_ref().storage, POOL_ADDR.to_string())?;
+        assert_eq!(pool_info.status, EraStatus::InitFailed);
+        let (pool_ica_info, _, _) =
+            INFO_OF_ICA_ID.load(deps.as_ref().storage, "ica".to_string())?;
+        assert!(!pool_ica_info.closed);
+        Ok(())
+    }
+
+    #[test]
+    fn timeout_runs_failed_callback_and_closes_channel() -> NeutronResult<()> {
+        let mut deps = setup()?;
+
+        let res = sudo_timeout(deps.as_mut(), request())?;
+
+        assert_eq!(res.attributes[0].key, "closed_channel_id");
+        assert_eq!(res.attributes[0].value, CHANNEL_ID);
+        let pool_info = POOLS.load(deps.as_ref().storage, POOL_ADDR.to_string())?;
+        assert_eq!(pool_info.status, EraStatus::InitFailed);
+        let (pool_ica_info, withdraw_ica_info, _) =
+            INFO_OF_ICA_ID.load(deps.as_ref().storage, "ica".to_string())?;
+        assert!(pool_ica_info.closed);
+        assert!(!withdraw_ica_info.closed);
+        Ok(())
     }
 }
@@ -0,0 +1,261 @@
+use crate::error_conversion::ContractError;
+use crate::helper::{CAL_BASE, MAX_UNBOND_COMMISSION_TIERS};
+use crate::state::{PoolInfo, StakeRecord, UnbondCommissionSchedule, STAKE_RECORDS};
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use neutron_sdk::NeutronResult;
+use std::ops::{Add, Div, Mul, Sub};
+
+pub fn check_unbond_commission_schedule(schedule: &UnbondCommissionSchedule) -> NeutronResult<()> {
+    match schedule {
+        UnbondCommissionSchedule::Flat => {}
+        UnbondCommissionSchedule::HoldTime { tiers } => {
+            if tiers.is_empty() || tiers.len() > MAX_UNBOND_COMMISSION_TIERS {
+                return Err(ContractError::UnbondCommissionScheduleNotMatch {}.into());
+            }
+            for (i, tier) in tiers.iter().enumerate() {
+                if tier.commission > CAL_BASE
+                    || (i > 0 && tier.min_hold_seconds <= tiers[i - 1].min_hold_seconds)
+                {
+                    return Err(ContractError::UnbondCommissionScheduleNotMatch {}.into());
+                }
+            }
+        }
+        UnbondCommissionSchedule::Size { tiers } => {
+            if tiers.is_empty() || tiers.len() > MAX_UNBOND_COMMISSION_TIERS {
+                return Err(ContractError::UnbondCommissionScheduleNotMatch {}.into());
+            }
+            for (i, tier) in tiers.iter().enumerate() {
+                if tier.commission > CAL_BASE
+                    || (i > 0 && tier.min_lsd_token_amount <= tiers[i - 1].min_lsd_token_amount)
+                {
+                    return Err(ContractError::UnbondCommissionScheduleNotMatch {}.into());
+                }
+            }
+        }
+        UnbondCommissionSchedule::Decay {
+            initial_commission,
+            decay_seconds,
+        } => {
+            if *initial_commission > CAL_BASE || *decay_seconds == 0 {
+                return Err(ContractError::UnbondCommissionScheduleNotMatch {}.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// commission of an unstake, unbond_commission applies below the first tier and after the decay
+pub fn unbond_commission_of(
+    pool_info: &PoolInfo,
+    lsd_token_amount: Uint128,
+    held_seconds: u64,
+) -> Uint128 {
+    let base = pool_info.unbond_commission;
+    match &pool_info.unbond_commission_schedule {
+        UnbondCommissionSchedule::Flat => base,
+        UnbondCommissionSchedule::HoldTime { tiers } => tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_hold_seconds <= held_seconds)
+            .map(|tier| tier.commission)
+            .unwrap_or(base),
+        UnbondCommissionSchedule::Size { tiers } => tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_lsd_token_amount <= lsd_token_amount)
+            .map(|tier| tier.commission)
+            .unwrap_or(base),
+        // falls linearly from initial_commission to unbond_commission
+        UnbondCommissionSchedule::Decay {
+            initial_commission,
+            decay_seconds,
+        } => {
+            if held_seconds >= *decay_seconds || *initial_commission <= base {
+                return base;
+            }
+            initial_commission.sub(
+                initial_commission
+                    .sub(base)
+                    .multiply_ratio(held_seconds, *decay_seconds),
+            )
+        }
+    }
+}
+
+// held seconds, commission and fee of an unstake, the aged rate of a hold time schedule only
+// covers the recorded lsd tokens, tokens received by transfer or minted before the records
+// existed pay unbond_commission
+pub fn unbond_fee_of(
+    storage: &dyn Storage,
+    pool_info: &PoolInfo,
+    user: Addr,
+    pool_addr: String,
+    lsd_token_amount: Uint128,
+    now: u64,
+) -> StdResult<(u64, Uint128, Uint128)> {
+    let record = STAKE_RECORDS.may_load(storage, (user, pool_addr))?;
+    let held_seconds = record
+        .as_ref()
+        .map(|record| now.saturating_sub(record.staked_at))
+        .unwrap_or(0);
+
+    let aged_amount = match pool_info.unbond_commission_schedule {
+        UnbondCommissionSchedule::HoldTime { .. } | UnbondCommissionSchedule::Decay { .. } => {
+            record
+                .map(|record| record.lsd_token_amount.min(lsd_token_amount))
+                .unwrap_or_default()
+        }
+        _ => lsd_token_amount,
+    };
+    let untracked_amount = lsd_token_amount.sub(aged_amount);
+
+    let fee = aged_amount
+        .mul(unbond_commission_of(
+            pool_info,
+            lsd_token_amount,
+            held_seconds,
+        ))
+        .div(CAL_BASE)
+        .add(
+            untracked_amount
+                .mul(pool_info.unbond_commission)
+                .div(CAL_BASE),
+        );
+    let commission = if lsd_token_amount.is_zero() {
+        pool_info.unbond_commission
+    } else {
+        fee.multiply_ratio(CAL_BASE, lsd_token_amount)
+    };
+
+    Ok((held_seconds, commission, fee))
+}
+
+// a new stake moves the record to the amount weighted average time
+pub fn record_stake(
+    storage: &mut dyn Storage,
+    user: Addr,
+    pool_addr: String,
+    lsd_token_amount: Uint128,
+    now: u64,
+) -> StdResult<()> {
+    let key = (user, pool_addr);
+    let record = match STAKE_RECORDS.may_load(storage, key.clone())? {
+        Some(record) if !record.lsd_token_amount.is_zero() => {
+            let total = record.lsd_token_amount.add(lsd_token_amount);
+            let staked_at = Uint128::from(record.staked_at)
+                .mul(record.lsd_token_amount)
+                .add(Uint128::from(now).mul(lsd_token_amount))
+                .div(total);
+            StakeRecord {
+                lsd_token_amount: total,
+                staked_at: staked_at.u128() as u64,
+            }
+        }
+        _ => StakeRecord {
+            lsd_token_amount,
+            staked_at: now,
+        },
+    };
+
+    STAKE_RECORDS.save(storage, key, &record)
+}
+
+pub fn consume_stake_record(
+    storage: &mut dyn Storage,
+    user: Addr,
+    pool_addr: String,
+    lsd_token_amount: Uint128,
+) -> StdResult<()> {
+    let key = (user, pool_addr);
+    let Some(mut record) = STAKE_RECORDS.may_load(storage, key.clone())? else {
+        return Ok(());
+    };
+
+    record.lsd_token_amount = record.lsd_token_amount.saturating_sub(lsd_token_amount);
+    if record.lsd_token_amount.is_zero() {
+        STAKE_RECORDS.remove(storage, key);
+        return Ok(());
+    }
+
+    STAKE_RECORDS.save(storage, key, &record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{tests::BASELINE_POOL_INFO, HoldTimeTier};
+    use cosmwasm_std::{from_json, testing::MockStorage};
+
+    fn pool_info(schedule: UnbondCommissionSchedule) -> StdResult<PoolInfo> {
+        let mut pool_info: PoolInfo = from_json(BASELINE_POOL_INFO)?;
+        pool_info.unbond_commission = Uint128::new(10_000);
+        pool_info.unbond_commission_schedule = schedule;
+        Ok(pool_info)
+    }
+
+    fn hold_time() -> UnbondCommissionSchedule {
+        UnbondCommissionSchedule::HoldTime {
+            tiers: vec![HoldTimeTier {
+                min_hold_seconds: 1_000,
+                commission: Uint128::zero(),
+            }],
+        }
+    }
+
+    #[test]
+    fn aged_rate_only_covers_recorded_amount() -> StdResult<()> {
+        let mut storage = MockStorage::new();
+        let pool_info = pool_info(hold_time())?;
+        let user = Addr::unchecked("user");
+        record_stake(
+            &mut storage,
+            user.clone(),
+            "pool".to_string(),
+            Uint128::new(100),
+            0,
+        )?;
+
+        let (held_seconds, commission, fee) = unbond_fee_of(
+            &storage,
+            &pool_info,
+            user,
+            "pool".to_string(),
+            Uint128::new(300),
+            2_000,
+        )?;
+        assert_eq!(held_seconds, 2_000);
+        // 100 aged at 0%, 200 untracked at 1%
+        assert_eq!(fee, Uint128::new(2));
+        assert_eq!(commission, Uint128::new(6_666));
+        Ok(())
+    }
+
+    #[test]
+    fn missing_record_pays_unbond_commission() -> StdResult<()> {
+        let storage = MockStorage::new();
+        let user = Addr::unchecked("user");
+        let schedules = [
+            hold_time(),
+            UnbondCommissionSchedule::Decay {
+                initial_commission: Uint128::new(50_000),
+                decay_seconds: 1_000,
+            },
+        ];
+        for schedule in schedules {
+            let pool_info = pool_info(schedule)?;
+            let (held_seconds, commission, fee) = unbond_fee_of(
+                &storage,
+                &pool_info,
+                user.clone(),
+                "pool".to_string(),
+                Uint128::new(300),
+                2_000,
+            )?;
+            assert_eq!(held_seconds, 0);
+            assert_eq!(commission, Uint128::new(10_000));
+            assert_eq!(fee, Uint128::new(3));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use crate::error_conversion::ContractError;
+use crate::helper::{check_ibc_fee, gen_redelegate_txs, tx_timeout_seconds};
+use crate::query::query_validator_by_addr;
+use crate::redelegation::{
+    check_redelegation, load_redelegation_limits, settle_redelegating_pairs,
+};
+use crate::state::{
+    PoolInfo, SudoPayload, TxType, ValidatorStatusEvent, ValidatorStatusEventKind,
+    ValidatorUpdateStatus, INFO_OF_ICA_ID, POOLS, VALIDATOR_STATUS_EVENTS,
+};
+use crate::state::{SudoData, SUDO_PAYLOAD_VERSION};
+use crate::tx_callback::msg_with_sudo_callback;
+use crate::validator_selection::{split_by_weight_among, validator_weight};
+use cosmwasm_std::{Delegation, DepsMut, Env, Event, MessageInfo, Response, SubMsg};
+use neutron_sdk::{
+    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    NeutronResult,
+};
+
+// staking BondStatus of the host chain
+const BOND_STATUS_UNBONDED: i32 = 1;
+const BOND_STATUS_UNBONDING: i32 = 2;
+
+// refreshes the pool's inactive validators from the validators ICQ result and records the
+// status changes of the era, nothing happens if the ICQ has no result yet
+pub fn update_inactive_validators(
+    deps: DepsMut<NeutronQuery>,
+    pool_addr: String,
+    pool_info: &mut PoolInfo,
+) -> NeutronResult<Vec<Event>> {
+    let Ok(validators) = query_validator_by_addr(deps.as_ref(), pool_addr.clone()) else {
+        return Ok(vec![]);
+    };
+
+    let mut inactive_validators = vec![];
+    let mut status_events = vec![];
+    for validator in validators.validator.validators {
+        if !pool_info
+            .validator_addrs
+            .contains(&validator.operator_address)
+        {
+            continue;
+        }
+        let kind = if validator.jailed {
+            ValidatorStatusEventKind::Jailed
+        } else if validator.status == BOND_STATUS_UNBONDING {
+            ValidatorStatusEventKind::Unbonding
+        } else if validator.status == BOND_STATUS_UNBONDED {
+            ValidatorStatusEventKind::Unbonded
+        } else {
+            continue;
+        };
+
+        if !pool_info
+            .inactive_validators
+            .contains(&validator.operator_address)
+        {
+            status_events.push(ValidatorStatusEvent {
+                era: pool_info.era,
+                validator: validator.operator_address.clone(),
+                kind,
+                amount: Default::default(),
+            });
+        }
+        inactive_validators.push(validator.operator_address);
+    }
+    for validator in pool_info.inactive_validators.iter() {
+        if !inactive_validators.contains(validator) && pool_info.validator_addrs.contains(validator)
+        {
+            status_events.push(ValidatorStatusEvent {
+                era: pool_info.era,
+                validator: validator.clone(),
+                kind: ValidatorStatusEventKind::Reactivated,
+                amount: Default::default(),
+            });
+        }
+    }
+    pool_info.inactive_validators = inactive_validators;
+
+    save_validator_status_events(deps, pool_addr, pool_info.era, status_events)
+}
+
+// moves the stake of inactive validators to the active ones in proportion to their weights,
+// skipping redelegations the host chain would reject. the caller attaches the ibc fee, without
+// it the redelegation is left for a later era
+pub fn redelegate_inactive_msg(
+    mut deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    info: &MessageInfo,
+    pool_addr: String,
+    pool_info: &mut PoolInfo,
+    delegations: &[Delegation],
+) -> NeutronResult<(Option<SubMsg<NeutronMsg>>, Vec<Event>)> {
+    if !pool_info.auto_redelegate_inactive
+        || pool_info.inactive_validators.is_empty()
+        || pool_info.validator_update_status != ValidatorUpdateStatus::End
+    {
+        return Ok((None, vec![]));
+    }
+    // retried on a later era once the ica channel is reopened
+    let (pool_ica_info, _, _) = INFO_OF_ICA_ID.load(deps.storage, pool_info.ica_id.clone())?;
+    if pool_ica_info.closed {
+        return Ok((None, vec![]));
+    }
+
+    let limits = load_redelegation_limits(
+        deps.storage,
+        pool_info,
+        pool_addr.clone(),
+        env.block.time.seconds(),
+    )?;
+    let active_validators: Vec<String> = pool_info
+        .validator_addrs
+        .iter()
+        .filter(|validator| !validator_weight(pool_info, validator).is_zero())
+        .cloned()
+        .collect();
+
+    let mut msgs = vec![];
+    let mut pairs = vec![];
+    let mut status_events = vec![];
+    for delegation in delegations {
+        if !pool_info
+            .inactive_validators
+            .contains(&delegation.validator)
+            || delegation.amount.amount.is_zero()
+        {
+            continue;
+        }
+        let dst_validators: Vec<String> = active_validators
+            .iter()
+            .filter(|dst| check_redelegation(&limits, &delegation.validator, dst).is_none())
+            .cloned()
+            .collect();
+        let plan = split_by_weight_among(pool_info, &dst_validators, delegation.amount.amount);
+        if plan.is_empty() {
+            continue;
+        }
+
+        for info in plan {
+            pairs.push((delegation.validator.clone(), info.validator.clone()));
+            msgs.push(gen_redelegate_txs(
+                pool_addr.clone(),
+                delegation.validator.clone(),
+                info.validator,
+                pool_info.remote_denom.clone(),
+                info.delegate_amount,
+            ));
+        }
+        status_events.push(ValidatorStatusEvent {
+            era: pool_info.era,
+            validator: delegation.validator.clone(),
+            kind: ValidatorStatusEventKind::Redelegated,
+            amount: delegation.amount.amount,
+        });
+    }
+    if msgs.is_empty() {
+        return Ok((None, vec![]));
+    }
+
+    // era active stays permissionless, a keeper without fee only skips the redelegation
+    let Ok(ibc_fee) = check_ibc_fee(deps.as_ref(), info) else {
+        return Ok((
+            None,
+            vec![Event::new("redelegate_inactive_skipped")
+                .add_attribute("pool", pool_addr)
+                .add_attribute("era", pool_info.era.to_string())
+                .add_attribute("reason", "ibc_fee_not_attached")],
+        ));
+    };
+    let submsg = msg_with_sudo_callback(
+        deps.branch(),
+        NeutronMsg::submit_tx(
+            pool_ica_info.ctrl_connection_id.clone(),
+            pool_info.ica_id.clone(),
+            msgs,
+            "".to_string(),
+            tx_timeout_seconds(pool_info, &TxType::RedelegateInactive),
+            ibc_fee,
+        ),
+        SudoPayload {
+            port_id: pool_ica_info.ctrl_port_id,
+            pool_addr: pool_addr.clone(),
+            version: SUDO_PAYLOAD_VERSION,
+            data: SudoData::Redelegate {
+                redelegating_pairs: pairs,
+            },
+            tx_type: TxType::RedelegateInactive,
+        },
+    )?;
+
+    pool_info.validator_update_status = ValidatorUpdateStatus::Start;
+
+    let events = save_validator_status_events(deps, pool_addr, pool_info.era, status_events)?;
+
+    Ok((Some(submsg), events))
+}
+
+fn save_validator_status_events(
+    deps: DepsMut<NeutronQuery>,
+    pool_addr: String,
+    era: u64,
+    status_events: Vec<ValidatorStatusEvent>,
+) -> NeutronResult<Vec<Event>> {
+    if status_events.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let events = status_events
+        .iter()
+        .map(|status_event| {
+            Event::new(status_event.kind.event_type())
+                .add_attribute("pool", pool_addr.clone())
+                .add_attribute("era", era.to_string())
+                .add_attribute("validator", status_event.validator.clone())
+                .add_attribute("amount", status_event.amount)
+        })
+        .collect();
+
+    let mut era_events = VALIDATOR_STATUS_EVENTS
+        .may_load(deps.storage, (pool_addr.clone(), era))?
+        .unwrap_or_default();
+    era_events.extend(status_events);
+    VALIDATOR_STATUS_EVENTS.save(deps.storage, (pool_addr, era), &era_events)?;
+
+    Ok(events)
+}
+
+pub fn sudo_redelegate_inactive_callback(
+    deps: DepsMut,
+    env: Env,
+    payload: SudoPayload,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let SudoData::Redelegate { redelegating_pairs } = payload.data else {
+        return Err(ContractError::SudoPayloadNotMatch {}.into());
+    };
+    let mut pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
+
+    settle_redelegating_pairs(
+        deps.storage,
+        &pool_info,
+        payload.pool_addr.clone(),
+        redelegating_pairs,
+        env.block.time.seconds(),
+    )?;
+    pool_info.validator_update_status = ValidatorUpdateStatus::End;
+
+    POOLS.save(deps.storage, payload.pool_addr, &pool_info)?;
+
+    Ok(Response::new())
+}
+
+pub fn sudo_redelegate_inactive_failed_callback(
+    deps: DepsMut,
+    payload: SudoPayload,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let mut pool_info = POOLS.load(deps.storage, payload.pool_addr.clone())?;
+
+    pool_info.validator_update_status = ValidatorUpdateStatus::End;
+
+    POOLS.save(deps.storage, payload.pool_addr, &pool_info)?;
+
+    Ok(Response::new())
+}
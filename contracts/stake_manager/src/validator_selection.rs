@@ -0,0 +1,503 @@
+use crate::error_conversion::ContractError;
+use crate::helper::{STAKE_SPLIT_THRESHOLD, WEIGHT_BASE};
+use crate::redelegation::check_redelegation;
+use crate::state::{PoolInfo, RedelegationLimitsResponse, ValidatorSelectStrategy};
+use cosmwasm_std::{Delegation, Uint128};
+use neutron_sdk::NeutronResult;
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Sub};
+
+#[derive(Clone, Debug)]
+pub struct ValidatorDelegateInfo {
+    pub validator: String,
+    pub delegate_amount: Uint128,
+}
+
+// delegated amount of every pool validator, validators without delegation are zero
+pub fn pool_validator_delegations(
+    pool_info: &PoolInfo,
+    delegations: &[Delegation],
+) -> Vec<(String, Uint128)> {
+    pool_info
+        .validator_addrs
+        .iter()
+        .map(|validator| {
+            let delegated = delegations
+                .iter()
+                .find(|d| d.validator == *validator)
+                .map(|d| d.amount.amount)
+                .unwrap_or(Uint128::zero());
+            (validator.clone(), delegated)
+        })
+        .collect()
+}
+
+// target weight of a pool validator, every validator has the same weight if none configured
+// and inactive validators have none
+pub fn validator_weight(pool_info: &PoolInfo, validator: &str) -> Uint128 {
+    if pool_info
+        .inactive_validators
+        .iter()
+        .any(|inactive| inactive == validator)
+    {
+        return Uint128::zero();
+    }
+    if pool_info.validator_weights.is_empty() {
+        return Uint128::one();
+    }
+    pool_info
+        .validator_weights
+        .iter()
+        .find(|w| w.validator == validator)
+        .map(|w| Uint128::from(w.weight))
+        .unwrap_or(Uint128::zero())
+}
+
+// splits amount in proportion to the target weights, remainder to the first weighted validator
+pub fn split_by_weight(pool_info: &PoolInfo, amount: Uint128) -> Vec<ValidatorDelegateInfo> {
+    split_by_weight_among(pool_info, &pool_info.validator_addrs, amount)
+}
+
+// same as split_by_weight but only among the given validators
+pub fn split_by_weight_among(
+    pool_info: &PoolInfo,
+    validators: &[String],
+    amount: Uint128,
+) -> Vec<ValidatorDelegateInfo> {
+    let weights: Vec<(String, Uint128)> = validators
+        .iter()
+        .map(|validator| (validator.clone(), validator_weight(pool_info, validator)))
+        .filter(|(_, weight)| !weight.is_zero())
+        .collect();
+    let total_weight = weights
+        .iter()
+        .fold(Uint128::zero(), |acc, (_, weight)| acc.add(*weight));
+    if total_weight.is_zero() {
+        return vec![];
+    }
+
+    let mut plan: Vec<ValidatorDelegateInfo> = weights
+        .iter()
+        .map(|(validator, weight)| ValidatorDelegateInfo {
+            validator: validator.clone(),
+            delegate_amount: amount.multiply_ratio(*weight, total_weight),
+        })
+        .collect();
+    let allocated = plan
+        .iter()
+        .fold(Uint128::zero(), |acc, info| acc.add(info.delegate_amount));
+    plan[0].delegate_amount = plan[0].delegate_amount.add(amount.sub(allocated));
+
+    plan.into_iter()
+        .filter(|info| !info.delegate_amount.is_zero())
+        .collect()
+}
+
+// orders delegations by how far they are above their target after unbond, largest first
+pub fn sort_delegations_for_unbond(
+    pool_info: &PoolInfo,
+    delegations: &[Delegation],
+    unbond_amount: Uint128,
+) -> Vec<Delegation> {
+    let total_delegated = delegations
+        .iter()
+        .fold(Uint128::zero(), |acc, d| acc.add(d.amount.amount));
+    let total_after = total_delegated.saturating_sub(unbond_amount);
+    let total_weight = pool_info
+        .validator_addrs
+        .iter()
+        .fold(Uint128::zero(), |acc, v| {
+            acc.add(validator_weight(pool_info, v))
+        });
+
+    let excess = |delegation: &Delegation| -> (Uint128, Uint128) {
+        // delegations to validators out of the pool have zero target
+        let target = if total_weight.is_zero()
+            || !pool_info.validator_addrs.contains(&delegation.validator)
+        {
+            Uint128::zero()
+        } else {
+            total_after.multiply_ratio(
+                validator_weight(pool_info, &delegation.validator),
+                total_weight,
+            )
+        };
+        (
+            delegation.amount.amount.saturating_sub(target),
+            target.saturating_sub(delegation.amount.amount),
+        )
+    };
+
+    let mut sorted_delegations = delegations.to_vec();
+    sorted_delegations.sort_by(|a, b| {
+        let (a_over, a_under) = excess(a);
+        let (b_over, b_under) = excess(b);
+        b_over
+            .cmp(&a_over)
+            .then(a_under.cmp(&b_under))
+            .then(b.amount.amount.cmp(&a.amount.amount))
+    });
+
+    sorted_delegations
+}
+
+#[derive(Clone, Debug)]
+pub struct RedelegateInfo {
+    pub src_validator: String,
+    pub dst_validator: String,
+    pub amount: Uint128,
+}
+
+// redelegations that move every pool validator to its target weight, empty if no validator
+// drifts more than the pool's rebalance tolerance. validators that received a redelegation
+// are never used as source and full pairs are skipped for the next destination
+pub fn rebalance_plan(
+    pool_info: &PoolInfo,
+    delegations: &[Delegation],
+    limits: &RedelegationLimitsResponse,
+) -> Vec<RedelegateInfo> {
+    let candidates = pool_validator_delegations(pool_info, delegations);
+    let total_delegated = candidates
+        .iter()
+        .fold(Uint128::zero(), |acc, (_, delegated)| acc.add(*delegated));
+    let total_weight = candidates.iter().fold(Uint128::zero(), |acc, (v, _)| {
+        acc.add(validator_weight(pool_info, v))
+    });
+    if total_delegated.is_zero() || total_weight.is_zero() {
+        return vec![];
+    }
+
+    let mut over = vec![];
+    let mut under = vec![];
+    let mut drift_exceeded = false;
+    for (validator, delegated) in candidates.iter() {
+        let target =
+            total_delegated.multiply_ratio(validator_weight(pool_info, validator), total_weight);
+        let drift = if *delegated > target {
+            delegated.sub(target)
+        } else {
+            target.sub(*delegated)
+        };
+        if drift.multiply_ratio(WEIGHT_BASE, total_delegated)
+            > Uint128::from(pool_info.rebalance_tolerance)
+        {
+            drift_exceeded = true;
+        }
+        match delegated.cmp(&target) {
+            Ordering::Greater => over.push((validator.clone(), drift)),
+            Ordering::Less => under.push((validator.clone(), drift)),
+            Ordering::Equal => {}
+        }
+    }
+    if !drift_exceeded {
+        return vec![];
+    }
+
+    over.sort_by(|a, b| b.1.cmp(&a.1));
+    under.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut plan = vec![];
+    for (src_validator, mut excess) in over {
+        for (dst_validator, deficit) in under.iter_mut() {
+            if excess.is_zero() {
+                break;
+            }
+            if deficit.is_zero()
+                || check_redelegation(limits, &src_validator, dst_validator).is_some()
+            {
+                continue;
+            }
+            let amount = excess.min(*deficit);
+            plan.push(RedelegateInfo {
+                src_validator: src_validator.clone(),
+                dst_validator: dst_validator.clone(),
+                amount,
+            });
+            excess = excess.sub(amount);
+            *deficit = deficit.sub(amount);
+        }
+    }
+
+    plan.into_iter().filter(|r| !r.amount.is_zero()).collect()
+}
+
+// errors unless the plan delegates exactly amount, so an era never starts a partial bond
+pub fn check_plan_covers(plan: &[ValidatorDelegateInfo], amount: Uint128) -> NeutronResult<()> {
+    let planned = plan
+        .iter()
+        .fold(Uint128::zero(), |acc, info| acc.add(info.delegate_amount));
+    if planned != amount {
+        return Err(ContractError::DelegatePlanNotCovered {}.into());
+    }
+    Ok(())
+}
+
+// computes the delegation plan of amount with the pool's validator select strategy,
+// validators without target weight are never selected
+pub fn allocate_delegate_amount(
+    pool_info: &mut PoolInfo,
+    delegations: &[Delegation],
+    amount: Uint128,
+) -> NeutronResult<Vec<ValidatorDelegateInfo>> {
+    let candidates: Vec<(String, Uint128)> = pool_validator_delegations(pool_info, delegations)
+        .into_iter()
+        .filter(|(validator, _)| !validator_weight(pool_info, validator).is_zero())
+        .collect();
+    if candidates.is_empty() {
+        return Err(ContractError::ValidatorsEmpty {}.into());
+    }
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let plan = match pool_info.validator_select_strategy {
+        ValidatorSelectStrategy::Average => {
+            if amount < STAKE_SPLIT_THRESHOLD {
+                vec![(candidates[0].0.clone(), amount)]
+            } else if pool_info.validator_weights.is_empty() {
+                split_average(&candidates, amount)
+            } else {
+                let validators: Vec<String> = candidates.iter().map(|(v, _)| v.clone()).collect();
+                split_by_weight_among(pool_info, &validators, amount)
+                    .into_iter()
+                    .map(|info| (info.validator, info.delegate_amount))
+                    .collect()
+            }
+        }
+        ValidatorSelectStrategy::LowestDelegatedFirst => {
+            // compare delegated / weight without dividing
+            let mut lowest = &candidates[0];
+            for candidate in candidates.iter() {
+                if candidate.1.full_mul(validator_weight(pool_info, &lowest.0))
+                    < lowest.1.full_mul(validator_weight(pool_info, &candidate.0))
+                {
+                    lowest = candidate;
+                }
+            }
+            vec![(lowest.0.clone(), amount)]
+        }
+        ValidatorSelectStrategy::EqualizeTowardTarget => {
+            let weights: Vec<(String, Uint128, Uint128)> = candidates
+                .iter()
+                .map(|(validator, delegated)| {
+                    (
+                        validator.clone(),
+                        *delegated,
+                        validator_weight(pool_info, validator),
+                    )
+                })
+                .collect();
+            split_toward_target(&weights, amount)
+        }
+        ValidatorSelectStrategy::RoundRobin => {
+            let index = (pool_info.round_robin_index % candidates.len() as u64) as usize;
+            pool_info.round_robin_index = pool_info.round_robin_index.add(1);
+            vec![(candidates[index].0.clone(), amount)]
+        }
+    };
+
+    let plan: Vec<ValidatorDelegateInfo> = plan
+        .into_iter()
+        .filter(|(_, delegate_amount)| !delegate_amount.is_zero())
+        .map(|(validator, delegate_amount)| ValidatorDelegateInfo {
+            validator,
+            delegate_amount,
+        })
+        .collect();
+    check_plan_covers(&plan, amount)?;
+
+    Ok(plan)
+}
+
+fn split_average(candidates: &[(String, Uint128)], amount: Uint128) -> Vec<(String, Uint128)> {
+    let validator_count = candidates.len() as u128;
+    let amount_per_validator = amount.div(Uint128::from(validator_count));
+    let remainder = amount.sub(amount_per_validator.mul(Uint128::new(validator_count)));
+
+    candidates
+        .iter()
+        .enumerate()
+        .map(|(index, (validator, _))| {
+            // Add the remainder to the first validator
+            if index == 0 {
+                (validator.clone(), amount_per_validator.add(remainder))
+            } else {
+                (validator.clone(), amount_per_validator)
+            }
+        })
+        .collect()
+}
+
+// (validator, delegated, weight): every validator's target is its weight share of the total
+// after delegation, the amount is split in proportion to how far each one is below target
+fn split_toward_target(
+    weights: &[(String, Uint128, Uint128)],
+    amount: Uint128,
+) -> Vec<(String, Uint128)> {
+    let total_weight = weights
+        .iter()
+        .fold(Uint128::zero(), |acc, (_, _, weight)| acc.add(*weight));
+    if total_weight.is_zero() {
+        return vec![];
+    }
+    let total_after = weights
+        .iter()
+        .fold(amount, |acc, (_, delegated, _)| acc.add(*delegated));
+
+    let deficits: Vec<(String, Uint128)> = weights
+        .iter()
+        .map(|(validator, delegated, weight)| {
+            let target = total_after.multiply_ratio(*weight, total_weight);
+            (validator.clone(), target.saturating_sub(*delegated))
+        })
+        .collect();
+    let total_deficit = deficits
+        .iter()
+        .fold(Uint128::zero(), |acc, (_, deficit)| acc.add(*deficit));
+
+    let mut most_deficit_index = 0;
+    for (index, (_, deficit)) in deficits.iter().enumerate() {
+        if *deficit > deficits[most_deficit_index].1 {
+            most_deficit_index = index;
+        }
+    }
+    if total_deficit.is_zero() || amount < STAKE_SPLIT_THRESHOLD {
+        return vec![(deficits[most_deficit_index].0.clone(), amount)];
+    }
+
+    let mut plan: Vec<(String, Uint128)> = deficits
+        .iter()
+        .map(|(validator, deficit)| {
+            (
+                validator.clone(),
+                amount.multiply_ratio(*deficit, total_deficit),
+            )
+        })
+        .collect();
+    let allocated = plan.iter().fold(Uint128::zero(), |acc, (_, plan_amount)| {
+        acc.add(*plan_amount)
+    });
+    // Add the rounding remainder to the most under-delegated validator
+    plan[most_deficit_index].1 = plan[most_deficit_index].1.add(amount.sub(allocated));
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{tests::BASELINE_POOL_INFO, ValidatorWeight};
+    use cosmwasm_std::{coin, from_json, Addr, StdResult};
+
+    fn pool_info(strategy: ValidatorSelectStrategy) -> StdResult<PoolInfo> {
+        let mut pool_info: PoolInfo = from_json(BASELINE_POOL_INFO)?;
+        pool_info.validator_addrs = vec!["val_a".to_string(), "val_b".to_string()];
+        pool_info.validator_select_strategy = strategy;
+        Ok(pool_info)
+    }
+
+    fn weights(pool_info: &mut PoolInfo, weight_a: u64, weight_b: u64) {
+        pool_info.validator_weights = vec![
+            ValidatorWeight {
+                validator: "val_a".to_string(),
+                weight: weight_a,
+            },
+            ValidatorWeight {
+                validator: "val_b".to_string(),
+                weight: weight_b,
+            },
+        ];
+    }
+
+    fn delegations(amount_a: u128, amount_b: u128) -> Vec<Delegation> {
+        [("val_a", amount_a), ("val_b", amount_b)]
+            .into_iter()
+            .map(|(validator, amount)| Delegation {
+                delegator: Addr::unchecked("pool"),
+                validator: validator.to_string(),
+                amount: coin(amount, "uatom"),
+            })
+            .collect()
+    }
+
+    fn planned(plan: &[ValidatorDelegateInfo]) -> Vec<(String, u128)> {
+        plan.iter()
+            .map(|info| (info.validator.clone(), info.delegate_amount.u128()))
+            .collect()
+    }
+
+    #[test]
+    fn average_splits_by_weight_when_configured() -> NeutronResult<()> {
+        let mut pool_info = pool_info(ValidatorSelectStrategy::Average)?;
+        let plan =
+            allocate_delegate_amount(&mut pool_info, &delegations(0, 0), Uint128::new(20_001))?;
+        assert_eq!(
+            planned(&plan),
+            vec![("val_a".to_string(), 10_001), ("val_b".to_string(), 10_000)]
+        );
+
+        weights(&mut pool_info, 7_500, 2_500);
+        let plan =
+            allocate_delegate_amount(&mut pool_info, &delegations(0, 0), Uint128::new(20_000))?;
+        assert_eq!(
+            planned(&plan),
+            vec![("val_a".to_string(), 15_000), ("val_b".to_string(), 5_000)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lowest_delegated_first_compares_per_weight() -> NeutronResult<()> {
+        let mut pool_info = pool_info(ValidatorSelectStrategy::LowestDelegatedFirst)?;
+        let plan =
+            allocate_delegate_amount(&mut pool_info, &delegations(300, 200), Uint128::new(5))?;
+        assert_eq!(planned(&plan), vec![("val_b".to_string(), 5)]);
+
+        weights(&mut pool_info, 7_500, 2_500);
+        let plan =
+            allocate_delegate_amount(&mut pool_info, &delegations(300, 200), Uint128::new(5))?;
+        assert_eq!(planned(&plan), vec![("val_a".to_string(), 5)]);
+        Ok(())
+    }
+
+    #[test]
+    fn round_robin_skips_validators_without_weight() -> NeutronResult<()> {
+        let mut pool_info = pool_info(ValidatorSelectStrategy::RoundRobin)?;
+        weights(&mut pool_info, 0, 10_000);
+        for _ in 0..2 {
+            let plan =
+                allocate_delegate_amount(&mut pool_info, &delegations(0, 0), Uint128::new(5))?;
+            assert_eq!(planned(&plan), vec![("val_b".to_string(), 5)]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn equalize_toward_target_covers_the_amount() -> NeutronResult<()> {
+        let mut pool_info = pool_info(ValidatorSelectStrategy::EqualizeTowardTarget)?;
+        weights(&mut pool_info, 5_000, 5_000);
+        let plan = allocate_delegate_amount(
+            &mut pool_info,
+            &delegations(10_000, 0),
+            Uint128::new(30_001),
+        )?;
+        assert_eq!(
+            planned(&plan),
+            vec![("val_a".to_string(), 10_000), ("val_b".to_string(), 20_001)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn no_weighted_validator_fails_the_plan() -> StdResult<()> {
+        let mut pool_info = pool_info(ValidatorSelectStrategy::Average)?;
+        pool_info.inactive_validators = pool_info.validator_addrs.clone();
+        assert!(
+            allocate_delegate_amount(&mut pool_info, &delegations(0, 0), Uint128::new(5)).is_err()
+        );
+        assert!(split_by_weight(&pool_info, Uint128::new(5)).is_empty());
+        assert!(check_plan_covers(&[], Uint128::new(5)).is_err());
+        assert!(check_plan_covers(&[], Uint128::zero()).is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,871 @@
+use crate::error::ContractError;
+use crate::msg::{
+    AllNftInfoResponse, Approval as ApprovalView, ApprovalResponse, ApprovalsResponse,
+    ContractInfoResponse, Cw721ReceiveMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, MinterResponse,
+    NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse, QueryMsg,
+    TokensResponse, UnstakeClaim,
+};
+use crate::state::{
+    decrement_tokens, increment_tokens, token_count, tokens, Approval, ContractInfo, TokenInfo,
+    CONTRACT_INFO, MINTER, OPERATORS,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Storage,
+};
+use cw2::{ensure_from_older_version, set_contract_version};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:unstake_nft";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONTRACT_INFO.save(
+        deps.storage,
+        &ContractInfo {
+            name: msg.name,
+            symbol: msg.symbol,
+        },
+    )?;
+    MINTER.save(deps.storage, &deps.api.addr_validate(&msg.minter)?)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => execute_transfer_nft(deps, env, info, recipient, token_id),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => execute_send_nft(deps, env, info, contract, token_id, msg),
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => execute_approve(deps, env, info, spender, token_id, expires),
+        ExecuteMsg::Revoke { spender, token_id } => {
+            execute_revoke(deps, env, info, spender, token_id)
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            execute_approve_all(deps, env, info, operator, expires)
+        }
+        ExecuteMsg::RevokeAll { operator } => execute_revoke_all(deps, info, operator),
+        ExecuteMsg::Mint {
+            token_id,
+            owner,
+            token_uri,
+            extension,
+        } => execute_mint(deps, info, token_id, owner, token_uri, extension),
+        ExecuteMsg::Burn { token_id } => execute_burn(deps, info, token_id),
+        ExecuteMsg::Lock { token_id } => execute_set_locked(deps, info, token_id, true),
+        ExecuteMsg::Unlock { token_id } => execute_set_locked(deps, info, token_id, false),
+    }
+}
+
+pub fn execute_mint(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    extension: UnstakeClaim,
+) -> Result<Response, ContractError> {
+    if info.sender != MINTER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token = TokenInfo {
+        owner: deps.api.addr_validate(&owner)?,
+        approvals: vec![],
+        token_uri,
+        extension,
+        locked: false,
+    };
+    tokens().update(deps.storage, &token_id, |old| match old {
+        Some(_) => Err(ContractError::Claimed {}),
+        None => Ok(token),
+    })?;
+    increment_tokens(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("minter", info.sender)
+        .add_attribute("owner", owner)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    transfer_nft(deps, &env, &info, &recipient, &token_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_nft")
+        .add_attribute("sender", info.sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    transfer_nft(deps, &env, &info, &contract, &token_id)?;
+
+    let send = Cw721ReceiveMsg {
+        sender: info.sender.to_string(),
+        token_id: token_id.clone(),
+        msg,
+    };
+
+    Ok(Response::new()
+        .add_message(send.into_cosmos_msg(contract.clone())?)
+        .add_attribute("action", "send_nft")
+        .add_attribute("sender", info.sender)
+        .add_attribute("recipient", contract)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    update_approvals(deps, &env, &info, &spender, &token_id, true, expires)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve")
+        .add_attribute("sender", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    update_approvals(deps, &env, &info, &spender, &token_id, false, None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke")
+        .add_attribute("sender", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_approve_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    // reject expired data as invalid
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    OPERATORS.save(deps.storage, (&info.sender, &operator_addr), &expires)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_all")
+        .add_attribute("sender", info.sender)
+        .add_attribute("operator", operator))
+}
+
+pub fn execute_revoke_all(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator_addr = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, (&info.sender, &operator_addr));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_all")
+        .add_attribute("sender", info.sender)
+        .add_attribute("operator", operator))
+}
+
+pub fn execute_burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    // the claim is still withdrawable until the minter burns the token
+    if info.sender != MINTER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    tokens().load(deps.storage, &token_id)?;
+    tokens().remove(deps.storage, &token_id)?;
+    decrement_tokens(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("sender", info.sender)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_set_locked(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    locked: bool,
+) -> Result<Response, ContractError> {
+    if info.sender != MINTER.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut token = tokens().load(deps.storage, &token_id)?;
+    token.locked = locked;
+    tokens().save(deps.storage, &token_id, &token)?;
+
+    Ok(Response::new()
+        .add_attribute("action", if locked { "lock" } else { "unlock" })
+        .add_attribute("token_id", token_id))
+}
+
+fn transfer_nft(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: &str,
+    token_id: &str,
+) -> Result<TokenInfo, ContractError> {
+    let mut token = tokens().load(deps.storage, token_id)?;
+    // ensure we have permissions
+    check_can_send(deps.as_ref(), env, info, &token)?;
+    if token.locked {
+        return Err(ContractError::Locked {});
+    }
+    // set owner and remove existing approvals
+    token.owner = deps.api.addr_validate(recipient)?;
+    token.approvals = vec![];
+    tokens().save(deps.storage, token_id, &token)?;
+    Ok(token)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_approvals(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    spender: &str,
+    token_id: &str,
+    // if add == false, remove. if add == true, remove then set with this expiration
+    add: bool,
+    expires: Option<Expiration>,
+) -> Result<TokenInfo, ContractError> {
+    let mut token = tokens().load(deps.storage, token_id)?;
+    // ensure we have permissions
+    check_can_approve(deps.as_ref(), env, info, &token)?;
+
+    // update the approval list (remove any for the same spender before adding)
+    let spender_addr = deps.api.addr_validate(spender)?;
+    token.approvals.retain(|apr| apr.spender != spender_addr);
+
+    // only difference between approve and revoke
+    if add {
+        // reject expired data as invalid
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+        token.approvals.push(Approval {
+            spender: spender_addr,
+            expires,
+        });
+    }
+
+    tokens().save(deps.storage, token_id, &token)?;
+
+    Ok(token)
+}
+
+/// returns true iff the sender can execute approve or reject on the contract
+fn check_can_approve(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token: &TokenInfo,
+) -> Result<(), ContractError> {
+    // owner can approve
+    if token.owner == info.sender {
+        return Ok(());
+    }
+    // operator can approve
+    if is_operator(deps.storage, &env.block, &token.owner, &info.sender)? {
+        return Ok(());
+    }
+
+    Err(ContractError::Unauthorized {})
+}
+
+/// returns true iff the sender can transfer ownership of the token
+fn check_can_send(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token: &TokenInfo,
+) -> Result<(), ContractError> {
+    // owner can send
+    if token.owner == info.sender {
+        return Ok(());
+    }
+
+    // any non-expired token approval can send
+    if token
+        .approvals
+        .iter()
+        .any(|apr| apr.spender == info.sender && !apr.is_expired(&env.block))
+    {
+        return Ok(());
+    }
+
+    // operator can send
+    if is_operator(deps.storage, &env.block, &token.owner, &info.sender)? {
+        return Ok(());
+    }
+
+    Err(ContractError::Unauthorized {})
+}
+
+fn is_operator(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    owner: &Addr,
+    operator: &Addr,
+) -> StdResult<bool> {
+    Ok(OPERATORS
+        .may_load(storage, (owner, operator))?
+        .map(|expires| !expires.is_expired(block))
+        .unwrap_or(false))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => to_json_binary(&query_owner_of(
+            deps,
+            &env,
+            token_id,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::Approval {
+            token_id,
+            spender,
+            include_expired,
+        } => to_json_binary(&query_approval(
+            deps,
+            &env,
+            token_id,
+            spender,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::Approvals {
+            token_id,
+            include_expired,
+        } => to_json_binary(&ApprovalsResponse {
+            approvals: query_owner_of(deps, &env, token_id, include_expired.unwrap_or(false))?
+                .approvals,
+        }),
+        QueryMsg::AllOperators {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => to_json_binary(&query_operators(
+            deps,
+            &env,
+            owner,
+            include_expired.unwrap_or(false),
+            start_after,
+            limit,
+        )?),
+        QueryMsg::NumTokens {} => to_json_binary(&NumTokensResponse {
+            count: token_count(deps.storage)?,
+        }),
+        QueryMsg::ContractInfo {} => {
+            let info = CONTRACT_INFO.load(deps.storage)?;
+            to_json_binary(&ContractInfoResponse {
+                name: info.name,
+                symbol: info.symbol,
+            })
+        }
+        QueryMsg::NftInfo { token_id } => to_json_binary(&query_nft_info(deps, token_id)?),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => to_json_binary(&AllNftInfoResponse {
+            access: query_owner_of(
+                deps,
+                &env,
+                token_id.clone(),
+                include_expired.unwrap_or(false),
+            )?,
+            info: query_nft_info(deps, token_id)?,
+        }),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_json_binary(&query_all_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::Minter {} => to_json_binary(&MinterResponse {
+            minter: MINTER.load(deps.storage)?.to_string(),
+        }),
+    }
+}
+
+pub fn query_owner_of(
+    deps: Deps,
+    env: &Env,
+    token_id: String,
+    include_expired: bool,
+) -> StdResult<OwnerOfResponse> {
+    let token = tokens().load(deps.storage, &token_id)?;
+
+    Ok(OwnerOfResponse {
+        owner: token.owner.to_string(),
+        approvals: humanize_approvals(&env.block, &token, include_expired),
+    })
+}
+
+pub fn query_approval(
+    deps: Deps,
+    env: &Env,
+    token_id: String,
+    spender: String,
+    include_expired: bool,
+) -> StdResult<ApprovalResponse> {
+    let token = tokens().load(deps.storage, &token_id)?;
+
+    // token owner has absolute approval
+    if token.owner == spender {
+        return Ok(ApprovalResponse {
+            approval: ApprovalView {
+                spender: token.owner.to_string(),
+                expires: Expiration::Never {},
+            },
+        });
+    }
+
+    humanize_approvals(&env.block, &token, include_expired)
+        .into_iter()
+        .find(|apr| apr.spender == spender)
+        .map(|approval| ApprovalResponse { approval })
+        .ok_or_else(|| StdError::not_found("Approval not found"))
+}
+
+pub fn query_operators(
+    deps: Deps,
+    env: &Env,
+    owner: String,
+    include_expired: bool,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OperatorsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let operators = OPERATORS
+        .prefix(&owner_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, expires)) => include_expired || !expires.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| {
+            item.map(|(spender, expires)| ApprovalView {
+                spender: spender.to_string(),
+                expires,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(OperatorsResponse { operators })
+}
+
+pub fn query_nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse> {
+    let token = tokens().load(deps.storage, &token_id)?;
+
+    Ok(NftInfoResponse {
+        token_uri: token.token_uri,
+        extension: token.extension,
+    })
+}
+
+pub fn query_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let tokens = tokens()
+        .idx
+        .owner
+        .prefix(owner_addr)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+pub fn query_all_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let tokens = tokens()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(token_id, _)| token_id))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+fn humanize_approvals(
+    block: &BlockInfo,
+    token: &TokenInfo,
+    include_expired: bool,
+) -> Vec<ApprovalView> {
+    token
+        .approvals
+        .iter()
+        .filter(|apr| include_expired || !apr.is_expired(block))
+        .map(|apr| ApprovalView {
+            spender: apr.spender.to_string(),
+            expires: apr.expires,
+        })
+        .collect()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{from_json, OwnedDeps};
+
+    use super::*;
+
+    const MINTER_ADDR: &str = "stake_manager";
+
+    fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "rATOM unstake".to_string(),
+            symbol: "urATOM".to_string(),
+            minter: MINTER_ADDR.to_string(),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        deps
+    }
+
+    fn mint(deps: DepsMut, token_id: &str, owner: &str) -> Result<Response, ContractError> {
+        execute(
+            deps,
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            ExecuteMsg::Mint {
+                token_id: token_id.to_string(),
+                owner: owner.to_string(),
+                token_uri: None,
+                extension: UnstakeClaim {
+                    pool_addr: "pool".to_string(),
+                    unstake_index: token_id.parse().unwrap(),
+                },
+            },
+        )
+    }
+
+    #[test]
+    fn only_minter_can_mint() {
+        let mut deps = setup_contract();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: "alice".to_string(),
+                token_uri: None,
+                extension: UnstakeClaim {
+                    pool_addr: "pool".to_string(),
+                    unstake_index: 1,
+                },
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        mint(deps.as_mut(), "1", "alice").unwrap();
+        let err = mint(deps.as_mut(), "1", "bob").unwrap_err();
+        assert_eq!(err, ContractError::Claimed {});
+
+        let count: NumTokensResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::NumTokens {}).unwrap()).unwrap();
+        assert_eq!(count.count, 1);
+
+        let info: NftInfoResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::NftInfo {
+                    token_id: "1".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            info.extension,
+            UnstakeClaim {
+                pool_addr: "pool".to_string(),
+                unstake_index: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn transfer_and_approvals() {
+        let mut deps = setup_contract();
+        mint(deps.as_mut(), "1", "alice").unwrap();
+
+        let transfer = ExecuteMsg::TransferNft {
+            recipient: "bob".to_string(),
+            token_id: "1".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            transfer.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // an approved spender can transfer
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::Approve {
+                spender: "bob".to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), transfer).unwrap();
+
+        let owner: OwnerOfResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OwnerOf {
+                    token_id: "1".to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(owner.owner, "bob");
+        assert!(owner.approvals.is_empty());
+
+        // an operator can transfer on behalf of the owner
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            ExecuteMsg::ApproveAll {
+                operator: "carol".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("carol", &[]),
+            ExecuteMsg::TransferNft {
+                recipient: "carol".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+        let tokens: TokensResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Tokens {
+                    owner: "carol".to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(tokens.tokens, vec!["1".to_string()]);
+    }
+
+    #[test]
+    fn only_minter_can_burn() {
+        let mut deps = setup_contract();
+        mint(deps.as_mut(), "1", "alice").unwrap();
+
+        for sender in ["bob", "alice"] {
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::Burn {
+                    token_id: "1".to_string(),
+                },
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+        }
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            ExecuteMsg::Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+        let tokens: TokensResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AllTokens {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(tokens.tokens.is_empty());
+        assert_eq!(token_count(deps.as_ref().storage).unwrap(), 0);
+    }
+
+    #[test]
+    fn locked_token_cannot_be_transferred() {
+        let mut deps = setup_contract();
+        mint(deps.as_mut(), "1", "alice").unwrap();
+
+        let lock = ExecuteMsg::Lock {
+            token_id: "1".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            lock.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), mock_info(MINTER_ADDR, &[]), lock).unwrap();
+
+        let transfer = ExecuteMsg::TransferNft {
+            recipient: "bob".to_string(),
+            token_id: "1".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            transfer.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Locked {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            ExecuteMsg::Unlock {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), transfer).unwrap();
+    }
+}
@@ -0,0 +1,21 @@
+/*!
+This is a cw721 contract for unstake claims of the stake manager. It implements
+the [CW721 spec](https://github.com/CosmWasm/cw-nfts/blob/main/packages/cw721/README.md),
+every token is one unstake index of a pool and its owner is the one who may withdraw it.
+
+Implements:
+
+- [x] CW721 Base
+- [x] Metadata extension (pool and unstake index of the claim)
+- [x] Enumerable extension
+
+Only the minter (the stake manager) can mint and burn. It locks the token while the claim
+is withdrawn and burns it once the withdraw succeeds.
+*/
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
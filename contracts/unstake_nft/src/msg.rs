@@ -0,0 +1,227 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{to_json_binary, Binary, CosmosMsg, StdResult, WasmMsg};
+use cw_utils::Expiration;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    /// The minter is the only one who can create new NFTs, it is the stake manager
+    pub minter: String,
+}
+
+/// Metadata of an unstake claim
+#[cw_serde]
+pub struct UnstakeClaim {
+    pub pool_addr: String,
+    pub unstake_index: u64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Transfer is a base message to move a token to another account without triggering actions
+    TransferNft { recipient: String, token_id: String },
+    /// Send is a base message to transfer a token to a contract and trigger an action
+    /// on the receiving contract.
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    /// Allows operator to transfer / send the token from the owner's account.
+    /// If expiration is set, then this allowance has a time/height limit
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    /// Remove previously granted Approval
+    Revoke { spender: String, token_id: String },
+    /// Allows operator to transfer / send any token from the owner's account.
+    /// If expiration is set, then this allowance has a time/height limit
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    /// Remove previously granted ApproveAll permission
+    RevokeAll { operator: String },
+    /// Mint a new NFT, can only be called by the contract minter
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: UnstakeClaim,
+    },
+    /// Burn an NFT once its claim is withdrawn, can only be called by the contract minter
+    Burn { token_id: String },
+    /// Lock an NFT while its claim is being withdrawn, can only be called by the contract minter
+    Lock { token_id: String },
+    /// Unlock an NFT whose withdraw failed, can only be called by the contract minter
+    Unlock { token_id: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Return the owner of the given token, error if token does not exist
+    #[returns(OwnerOfResponse)]
+    OwnerOf {
+        token_id: String,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+    /// Return operator that can access all of the owner's tokens.
+    #[returns(ApprovalResponse)]
+    Approval {
+        token_id: String,
+        spender: String,
+        include_expired: Option<bool>,
+    },
+    /// Return approvals that a token has
+    #[returns(ApprovalsResponse)]
+    Approvals {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// List all operators that can access all of the owner's tokens
+    #[returns(OperatorsResponse)]
+    AllOperators {
+        owner: String,
+        /// unset or false will filter out expired items, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Total number of tokens issued
+    #[returns(NumTokensResponse)]
+    NumTokens {},
+    #[returns(ContractInfoResponse)]
+    ContractInfo {},
+    /// Returns the token uri and the claim of the token
+    #[returns(NftInfoResponse)]
+    NftInfo { token_id: String },
+    /// Returns the result of both `NftInfo` and `OwnerOf` as one query
+    #[returns(AllNftInfoResponse)]
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Returns all tokens owned by the given address, [] if unset.
+    #[returns(TokensResponse)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Requires pagination. Lists all token_ids controlled by the contract.
+    #[returns(TokensResponse)]
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(MinterResponse)]
+    Minter {},
+}
+
+#[cw_serde]
+pub struct Approval {
+    /// Account that can transfer/send the token
+    pub spender: String,
+    /// When the Approval expires (maybe Expiration::never)
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct OwnerOfResponse {
+    /// Owner of the token
+    pub owner: String,
+    /// If set this address is approved to transfer/send the token as well
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct ApprovalResponse {
+    pub approval: Approval,
+}
+
+#[cw_serde]
+pub struct ApprovalsResponse {
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    pub operators: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct NumTokensResponse {
+    pub count: u64,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub struct NftInfoResponse {
+    pub token_uri: Option<String>,
+    pub extension: UnstakeClaim,
+}
+
+#[cw_serde]
+pub struct AllNftInfoResponse {
+    pub access: OwnerOfResponse,
+    pub info: NftInfoResponse,
+}
+
+#[cw_serde]
+pub struct TokensResponse {
+    /// Contains all token_ids in lexicographical ordering
+    /// If there are more than `limit`, use `start_after` in future queries
+    /// to achieve pagination.
+    pub tokens: Vec<String>,
+}
+
+#[cw_serde]
+pub struct MinterResponse {
+    pub minter: String,
+}
+
+/// Cw721ReceiveMsg should be de/serialized under `ReceiveNft()` variant in a ExecuteMsg
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+impl Cw721ReceiveMsg {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = ReceiverExecuteMsg::ReceiveNft(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cw_serde]
+enum ReceiverExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,83 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
+
+use crate::msg::UnstakeClaim;
+
+#[cw_serde]
+pub struct ContractInfo {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub struct Approval {
+    /// Account that can transfer/send the token
+    pub spender: Addr,
+    /// When the Approval expires (maybe Expiration::never)
+    pub expires: Expiration,
+}
+
+impl Approval {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
+}
+
+#[cw_serde]
+pub struct TokenInfo {
+    /// The owner of the newly minted NFT
+    pub owner: Addr,
+    /// Approvals are stored here, as we clear them all upon transfer and cannot accumulate much
+    pub approvals: Vec<Approval>,
+    pub token_uri: Option<String>,
+    pub extension: UnstakeClaim,
+    /// The claim is being withdrawn, the token cannot be transferred
+    #[serde(default)]
+    pub locked: bool,
+}
+
+pub struct TokenIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, TokenInfo, String>,
+}
+
+impl<'a> IndexList<TokenInfo> for TokenIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo>> + '_> {
+        let v: Vec<&dyn Index<TokenInfo>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn tokens<'a>() -> IndexedMap<'a, &'a str, TokenInfo, TokenIndexes<'a>> {
+    let indexes = TokenIndexes {
+        owner: MultiIndex::new(
+            |_pk, d: &TokenInfo| d.owner.clone(),
+            "tokens",
+            "tokens__owner",
+        ),
+    };
+    IndexedMap::new("tokens", indexes)
+}
+
+pub const CONTRACT_INFO: Item<ContractInfo> = Item::new("nft_info");
+pub const MINTER: Item<Addr> = Item::new("minter");
+pub const TOKEN_COUNT: Item<u64> = Item::new("num_tokens");
+// (owner, operator) -> expiration
+pub const OPERATORS: Map<(&Addr, &Addr), Expiration> = Map::new("operators");
+
+pub fn token_count(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(TOKEN_COUNT.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn increment_tokens(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = token_count(storage)? + 1;
+    TOKEN_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
+pub fn decrement_tokens(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = token_count(storage)?.saturating_sub(1);
+    TOKEN_COUNT.save(storage, &val)?;
+    Ok(val)
+}